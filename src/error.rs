@@ -187,6 +187,214 @@ pub enum NFTPassError {
     /// User has active membership
     #[error("UserHasActiveMembership")]
     UserHasActiveMembership,
+
+    /// Membership should be activated
+    #[error("MembershipNotActivated")]
+    MembershipNotActivated,
+
+    /// Membership has expired
+    #[error("MembershipExpired")]
+    MembershipExpired,
+
+    /// Not enough uses left on the membership
+    #[error("NotEnoughUses")]
+    NotEnoughUses,
+
+    /// Wrong use count
+    #[error("WrongUseCount")]
+    WrongUseCount,
+
+    /// Invalid Membership Authority
+    #[error("InvalidMembershipAuthority")]
+    InvalidMembershipAuthority,
+
+    /// Invalid Use Authority Key
+    #[error("InvalidUseAuthorityKey")]
+    InvalidUseAuthorityKey,
+
+    /// Not enough uses allowed for the use authority
+    #[error("NotEnoughAllowedUses")]
+    NotEnoughAllowedUses,
+
+    /// Invalid Store Authority Key
+    #[error("InvalidStoreAuthorityKey")]
+    InvalidStoreAuthorityKey,
+
+    /// Store authority already exists
+    #[error("StoreAuthorityAlreadyExists")]
+    StoreAuthorityAlreadyExists,
+
+    /// Wrong number of redemptions
+    #[error("WrongRedemptionCount")]
+    WrongRedemptionCount,
+
+    /// Not enough redemptions allowed for the store authority
+    #[error("NotEnoughAllowedRedemptions")]
+    NotEnoughAllowedRedemptions,
+
+    /// Membership is still active
+    #[error("MembershipStillActive")]
+    MembershipStillActive,
+
+    /// Not enough balance in the payout account
+    #[error("InsufficientPayoutBalance")]
+    InsufficientPayoutBalance,
+
+    /// Wrong number of creators
+    #[error("WrongCreatorsLength")]
+    WrongCreatorsLength,
+
+    /// Creator shares should sum to 100
+    #[error("WrongCreatorsShares")]
+    WrongCreatorsShares,
+
+    /// Creator address is listed more than once
+    #[error("DuplicateCreatorAddress")]
+    DuplicateCreatorAddress,
+
+    /// Invalid Market Config Key
+    #[error("InvalidMarketConfigKey")]
+    InvalidMarketConfigKey,
+
+    /// Pass book price is not the price expected by the buyer
+    #[error("PriceMismatch")]
+    PriceMismatch,
+
+    /// Pass book mint is not the mint expected by the buyer
+    #[error("MintMismatch")]
+    MintMismatch,
+
+    /// Max per wallet should be greater than zero
+    #[error("WrongMaxPerWallet")]
+    WrongMaxPerWallet,
+
+    /// Sale start should be before sale end
+    #[error("WrongSaleWindow")]
+    WrongSaleWindow,
+
+    /// Pass book sale has not started yet
+    #[error("SaleNotStarted")]
+    SaleNotStarted,
+
+    /// Pass book sale has ended
+    #[error("SaleEnded")]
+    SaleEnded,
+
+    /// Wallet is not in the pass book allowlist
+    #[error("NotInAllowlist")]
+    NotInAllowlist,
+
+    /// Pass book can't be deleted while activated
+    #[error("PassBookIsActivated")]
+    PassBookIsActivated,
+
+    /// Memberships sold from the pass book may still be live
+    #[error("PassBookHasLiveMembers")]
+    PassBookHasLiveMembers,
+
+    /// Token account does not hold the pass edition
+    #[error("NotPassHolder")]
+    NotPassHolder,
+
+    /// Soulbound pass books must print passes from a master edition
+    #[error("SoulboundWithoutMasterEdition")]
+    SoulboundWithoutMasterEdition,
+
+    /// Store already has a collection
+    #[error("StoreCollectionAlreadyCreated")]
+    StoreCollectionAlreadyCreated,
+
+    /// Collection does not match the store collection
+    #[error("InvalidCollectionMint")]
+    InvalidCollectionMint,
+
+    /// Metadata account is not the metadata of the master edition mint
+    #[error("InvalidMetadataAccount")]
+    InvalidMetadataAccount,
+
+    /// Pass books printing passes can't have a refund window
+    #[error("RefundWindowWithMasterEdition")]
+    RefundWindowWithMasterEdition,
+
+    /// Membership purchase is not refundable anymore
+    #[error("RefundWindowClosed")]
+    RefundWindowClosed,
+
+    /// Membership uses were already consumed
+    #[error("MembershipAlreadyUsed")]
+    MembershipAlreadyUsed,
+
+    /// Refund window must end before the escrow hold period
+    #[error("RefundWindowExceedsHoldPeriod")]
+    RefundWindowExceedsHoldPeriod,
+
+    /// Escrow account does not match the pass book escrow
+    #[error("InvalidEscrowKey")]
+    InvalidEscrowKey,
+
+    /// Pass book does not hold revenue in escrow
+    #[error("EscrowNotEnabled")]
+    EscrowNotEnabled,
+
+    /// Escrow hold period is not over
+    #[error("EscrowLocked")]
+    EscrowLocked,
+
+    /// Escrow holds nothing to settle
+    #[error("EscrowEmpty")]
+    EscrowEmpty,
+
+    /// Escrow must be settled first
+    #[error("EscrowNotSettled")]
+    EscrowNotSettled,
+
+    /// Only memberships with a limited access period can be subscribed
+    #[error("SubscriptionRequiresAccess")]
+    SubscriptionRequiresAccess,
+
+    /// Subscriptions can't be paid in native SOL
+    #[error("SubscriptionRequiresToken")]
+    SubscriptionRequiresToken,
+
+    /// Subscription must be for at least one period
+    #[error("InvalidSubscriptionPeriods")]
+    InvalidSubscriptionPeriods,
+
+    /// Membership has no subscription
+    #[error("SubscriptionNotFound")]
+    SubscriptionNotFound,
+
+    /// Membership is not expired yet
+    #[error("SubscriptionNotDue")]
+    SubscriptionNotDue,
+
+    /// Token account does not match the subscription
+    #[error("InvalidSubscriptionTokenAccount")]
+    InvalidSubscriptionTokenAccount,
+
+    /// Pass book memberships can't be paused
+    #[error("PauseNotAllowed")]
+    PauseNotAllowed,
+
+    /// Only memberships with a limited access period can be paused
+    #[error("PauseRequiresAccess")]
+    PauseRequiresAccess,
+
+    /// Membership is paused
+    #[error("MembershipPaused")]
+    MembershipPaused,
+
+    /// Membership is not paused
+    #[error("MembershipNotPaused")]
+    MembershipNotPaused,
+
+    /// Membership has a subscription left to charge
+    #[error("MembershipHasSubscription")]
+    MembershipHasSubscription,
+
+    /// Membership was paused for all the days allowed in its period
+    #[error("PauseAllowanceUsed")]
+    PauseAllowanceUsed,
 }
 
 impl From<NFTPassError> for ProgramError {
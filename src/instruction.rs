@@ -8,7 +8,17 @@ use solana_program::{
     system_program, sysvar,
 };
 
-use crate::state::PayoutInfoArgs;
+use crate::{
+    find_market_config_program_address, find_membership_program_address,
+    find_pass_book_escrow, find_pass_collection_mint, find_pass_program_address, find_pass_store_program_address,
+    find_program_authority,
+    state::{Creator, MasterEditionArgs, PassBookRefundArgs, PassMintArgs, PayoutInfoArgs},
+};
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account},
+    state::{EDITION, EDITION_MARKER_BIT_SIZE},
+};
+use spl_associated_token_account::get_associated_token_address;
 
 /// Initialize a PackSet arguments
 #[repr(C)]
@@ -35,13 +45,41 @@ pub struct InitPassBookArgs {
     pub has_referrer: bool,
     /// Indicates the presence of a market place authority in the account list
     pub has_market_authority: bool,
+    /// Indicates the presence of a master edition to vault in the account list
+    pub has_master_edition: bool,
     /// The date after which referral rewards expires
     pub referral_end_date: Option<u64>,
+    /// Creators sharing the sales revenue, defaults to the authority alone
+    pub creators: Option<Vec<Creator>>,
+    /// The maximum number of passes a single wallet can buy
+    pub max_per_wallet: Option<u64>,
+    /// The date before which passes can't be bought
+    pub sale_start: Option<u64>,
+    /// The date after which passes can't be bought
+    pub sale_end: Option<u64>,
+    /// Merkle root of the wallets allowed to buy
+    pub allowlist_root: Option<[u8; 32]>,
+    /// Passes are frozen in the buyer wallet, requires a master edition
+    pub soulbound: bool,
+    /// Seconds after a purchase during which it can be refunded, can't be set with a master edition
+    pub refund_window: Option<u64>,
+    /// Seconds the revenue of each purchase is held in escrow, can't be shorter than the refund window
+    pub escrow_hold_period: Option<u64>,
+    /// Days a member can pause the membership for in each period bought, requires an access period
+    pub max_pause_days: Option<u64>,
 }
 
-/// Edit a PassBook arguments
+/// Delete a PassBook arguments
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct DeletePassBookArgs {
+    /// Delete even if memberships may still be live, refunding the ones provided
+    pub force: bool,
+}
+
+/// Edit a PassBook arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Default)]
 pub struct EditPassBookArgs {
     /// Name
     pub name: Option<String>,
@@ -53,12 +91,104 @@ pub struct EditPassBookArgs {
     pub price: Option<u64>,
     /// If true authority can make changes at deactivated phase
     pub mutable: Option<bool>,
+    /// Creators sharing the sales revenue
+    pub creators: Option<Vec<Creator>>,
+    /// The maximum number of passes a single wallet can buy, `Some(None)` removes the limit
+    pub max_per_wallet: Option<Option<u64>>,
+    /// The date before which passes can't be bought, `Some(None)` removes it
+    pub sale_start: Option<Option<u64>>,
+    /// The date after which passes can't be bought, `Some(None)` removes it
+    pub sale_end: Option<Option<u64>>,
+    /// Merkle root of the wallets allowed to buy, `Some(None)` opens the sale to everyone
+    pub allowlist_root: Option<Option<[u8; 32]>>,
 }
 
 /// Buy Pass arguments
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct BuyPassArgs {
+    /// The price the buyer agreed to pay, the purchase fails if the pass book price differs
+    pub expected_price: u64,
+    /// The mint the buyer agreed to pay with, the purchase fails if the pass book mint differs
+    pub expected_mint: Pubkey,
+    /// Proof the buyer wallet is in the allowlist, required when the pass book has one
+    pub allowlist_proof: Option<AllowlistProof>,
+}
+
+/// Subscribe Membership arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SubscribeArgs {
+    /// The price the member agreed to pay each period, the subscription fails if the pass book price differs
+    pub expected_price: u64,
+    /// The mint the member agreed to pay with, the subscription fails if the pass book mint differs
+    pub expected_mint: Pubkey,
+    /// Number of periods the membership is renewed for
+    pub periods: u64,
+}
+
+/// Allowlist membership proof
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct AllowlistProof {
+    /// The number of passes the wallet can buy, if the leaf has one
+    pub allocation: Option<u64>,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Use Membership arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UseMembershipArgs {
+    /// The number of uses to consume
+    pub count: u64,
+}
+
+/// Approve Use Authority arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ApproveUseAuthorityArgs {
+    /// The number of uses the use authority can consume
+    pub number_of_uses: u64,
+}
+
+/// Add Store Authority arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct AddStoreAuthorityArgs {
+    /// The number of redemptions the store authority can perform
+    pub allowed_redemptions: u64,
+}
+
+/// Top Up Store Authority arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct TopUpStoreAuthorityArgs {
+    /// The number of redemptions to add to the store authority quota
+    pub additional_redemptions: u64,
+}
+
+/// Set Expiry Bounty arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetExpiryBountyArgs {
+    /// Lamports paid from the store balance to whoever expires a membership
+    pub bounty: u64,
+}
+
+/// Withdraw arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct WithdrawArgs {
+    /// The amount to withdraw from the payout account
+    pub amount: u64,
+}
+
+/// SetMarketConfig arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetMarketConfigArgs {
     /// The fee in basis point for the market place owner
     pub market_fee_basis_point: u16,
     /// The percentage of the amount from market_fee_basis_point to reward to the referral account
@@ -67,6 +197,18 @@ pub struct BuyPassArgs {
     pub referral_kick_back_share: u8,
 }
 
+/// CreateStoreCollection arguments
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct CreateStoreCollectionArgs {
+    /// Name of the collection NFT
+    pub name: String,
+    /// Symbol of the collection NFT
+    pub symbol: String,
+    /// URI of the collection NFT
+    pub uri: String,
+}
+
 /// Instruction definition
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub enum NFTPassInstruction {
@@ -81,16 +223,32 @@ pub enum NFTPassInstruction {
     /// DeletePass Book
     ///
     /// Transfer all the SOL from pass book account to refunder account and thus remove it.
+    /// The pass book must not be activated. A vaulted master edition is returned to the new owner,
+    /// the vault is closed to the refunder and the master metadata update authority goes back to the authority.
+    /// Memberships sold from the pass book must have expired, unless forced: every membership referring
+    /// to the pass book must then be provided, active ones are expired and refunded by the authority
+    /// for the time left, in the price and mint they were bought at if the purchase is still recorded.
     ///
     /// Accounts:
     ///   0.  `[writable]` Pass book account with address as pda of (PDA ['pass', program id, master metadata mint id] )
     ///   1.  `[signer]` Authority of pass book account
     ///   2.  `[writable]` Refunder
-    ///   3.  `[writable]` Token account owned by pass book that holds the master edition
-    ///   4.  `[]` Mint account of the tok en   
-    ///   5.  `[]` SPL Token Program
-    ///   6.  `[writable]` New master edition owner
-    DeletePassBook,
+    ///   3.  `[writable]` Store (PDA ['passbook', program id, authority, 'store'])
+    ///   4.  `[]` Clock sysvar
+    ///   5.  `[writable]` Optional vault token account owned by program authority that holds the master edition
+    ///   6.  `[]` Optional mint account of the master edition
+    ///   7.  `[]` Optional SPL Token Program
+    ///   8.  `[writable]` Optional token account of the new master edition owner
+    ///   9.  `[]` Optional program authority (PDA ['passbook', program id])
+    ///   10. `[writable]` Optional master metadata account
+    ///   11. `[]` Optional token metadata program
+    ///   12. `[writable]` Authority token account paying the refunds (authority wallet for native SOL), if forced
+    ///   13. `[]` SPL Token Program (System program for native SOL), if forced
+    ///   14. `[writable]` Membership account and member token account (member wallet for native SOL) of each membership referring to the pass book, in order
+    ///
+    /// Parameters:
+    /// - force bool
+    DeletePassBook(DeletePassBookArgs),
     /// DeactivatePassBook
     ///
     /// Set pack state to "Deactivated"
@@ -106,32 +264,58 @@ pub enum NFTPassInstruction {
     /// Accounts:
     ///   0.  `[writable]` Pass book account with address as pda of (PDA ['pass', program id, master metadata mint id] )
     ///   1.  `[signer]` Authority of pass book account
+    ///   2.  `[signer]` payer
+    ///   3.  `[]` Rent info
+    ///   4.  `[]` System program
+    ///   5.  `[writable]` Master metadata account, when the pass book has a master edition and the name or uri change
+    ///   6.  `[]` Program authority with address as pda of (PDA ['passbook', program id] ), when the master metadata is passed
+    ///   7.  `[]` Token metadata program, when the master metadata is passed
+    ///   8.  `[writable]` Payout account and payout token account of each new creator, in order,
+    ///       or of each current creator when only the price mint changes
+    ///   9.  `[]` Optional new price mint
+    ///
+    /// Name and uri are copied to the master metadata unless it is immutable.
     ///
     /// Parameters:
     /// - name Option<String>
     /// - description Option<String>
     /// - URI Option<String>
     /// - mutable	Option<bool> (only can be changed from true to false)
+    /// - creators Option<Vec<Creator>>
+    /// - max_per_wallet Option<Option<u64>>
+    /// - sale_start Option<Option<u64>>
+    /// - sale_end Option<Option<u64>>
+    /// - allowlist_root Option<Option<[u8; 32]>>
     EditPassBook(EditPassBookArgs),
     /// InitPassBook
     ///
     /// Initialize created account.
+    /// A master edition can be vaulted to print an edition to every buyer.
     ///
     /// Accounts:
-    ///   0.  `[writable]` Uninitialized master pass account with address as pda of (PDA ['pass', program id, master metadata mint id] )
-    ///   1.  `[writer]` Source token account that holds MasterEdition token
-    ///   2.  `[writer]` token_account (program account to hold MasterEdition token)
-    ///   3.  `[writer]` Store account with pda of ['pass', program id, authority, 'store']
-    ///   4.  `[signer]` Authority of pass account
-    ///   5.  `[signer]` payer
-    ///   6.  `[]` Master Metadata mint
-    ///   7.  `[]` Master record metadata account
-    ///   8.  `[]` Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])
-    ///   9.  `[]` Price mint
-    ///   10. `[]` Clock info
-    ///   11. `[]` Rent info
-    ///   12. `[]` System program
-    ///   13. `[]` Token program
+    ///   0.  `[writable, signer]` Uninitialized pass book account, with a master edition the pda of (PDA ['passbook', program id, master edition mint] ) not signing
+    ///   1.  `[writable]` Store account with pda of ['passbook', program id, authority, 'store']
+    ///   2.  `[signer]` Authority of pass account
+    ///   3.  `[signer]` payer
+    ///   4.  `[]` Price mint
+    ///   5.  `[]` Clock info
+    ///   6.  `[]` Rent info
+    ///   7.  `[]` System program
+    ///   8.  `[writable]` Optional source token account of the authority holding the master edition token
+    ///   9.  `[writable]` Optional vault token account, associated token account of the program authority
+    ///   10. `[]` Optional program authority with address as pda of (PDA ['passbook', program id] )
+    ///   11. `[]` Optional master edition mint
+    ///   12. `[]` Optional master metadata account, its update authority must be the program authority
+    ///   13. `[]` Optional master edition V2 account
+    ///   14. `[]` Optional SPL Token program
+    ///   15. `[]` Optional associated token account program
+    ///   16. `[writable]` Payout account and payout token account of each creator, in order
+    ///   17. `[signer]` Optional market place authority
+    ///   18. `[writable]` Optional market place payout account
+    ///   19. `[writable]` Optional market place payout token account
+    ///   20. `[]` Optional referral user wallet
+    ///   21. `[writable]` Optional referral payout account
+    ///   22. `[writable]` Optional referral payout token account
     ///
     /// Parameters:
     /// - name	String
@@ -140,42 +324,429 @@ pub enum NFTPassInstruction {
     /// - mutable	bool
     /// - period    Period
     /// - max_num_uses    Option<u64>    InitPassBook()
+    /// - creators Option<Vec<Creator>>
+    /// - max_per_wallet Option<u64>
+    /// - sale_start Option<u64>
+    /// - sale_end Option<u64>
+    /// - allowlist_root Option<[u8; 32]>
+    /// - soulbound bool
+    /// - refund_window Option<u64>
+    /// - escrow_hold_period Option<u64>
+    /// - max_pause_days Option<u64>
     InitPassBook(InitPassBookArgs),
     /// Buy Pass
     ///
     /// Buy a pass from a Pass Book.
+    /// Prints an edition of the vaulted master edition to the buyer if the pass book has one.
     ///
     /// Accounts:
-    ///   0.   `[writable]` Pass book account with address as pda of (PDA ['pass', program id, master metadata mint id] )
-    ///   1.   `[writable]` The pass store account with address as pda of (PDA ['pass', program id, authority, 'store'] )
-    ///   2.   `[writable]` Pass book token account vaulted that holds the master edition
-    ///   3.   `[signer]`   The wallet of the user making the purchase
-    ///   4.   `[writable]` Token account owned by user wallet used for transfer
-    ///   5.   `[signer]`   The fee payer 
-    ///   6.   `[writable]` New metadata account   || Will be created by mpl_token_metadata
-    ///   7.   `[writable]` New edition account    || Will be created by mpl_token_metadata
-    ///   8.   `[writable]` New mint account 
-    ///   9.   `[writable]` Master edition account 
-    ///   10.  `[writable]` Master metadata account 
-    ///   11.  `[writable]` Edition marker account  || Will be created by `mpl_token_metadata
-    ///   12.  `[writable]` New token account that holds limited edition
-    ///   12.  `[writable]` Trade history 
-    ///   13.  `[writable]` Creator payout info account []
-    ///   14.  `[writable]` Creator payout token account []
-    ///   15.  `[writable]` Creator payout ticket account [] || Will be created by nft_  pass
-    ///   16.  `[signer]`   Market place authority
-    ///   17.  `[writable]` Market place payout info account
-    ///   18.  `[writable]` Market place payout token account
-    ///   19.  `[writable]` Market place payout ticker account || Will be created by nft_  pass
-    ///   20.  `[]`         Referral user wallet
-    ///   21.  `[writable]` Referral payout info account
-    ///   22.  `[writable]` Referral payout token account
-    ///   23.  `[writable]` Referral payout ticket account || Will be created by nft_  pass
-    ///   24.  `[]` Mint account of the token   
-    ///   25.  `[]` SPL Token Program
-    ///   26.  `[writable]` New master edition owner
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[signer]`   The wallet of the user making the purchase
+    ///   3.   `[writable]` Token account owned by user wallet used for transfer
+    ///   4.   `[signer]`   The fee payer
+    ///   5.   `[writable]` Trade history with address as pda of (PDA ['passbook', program id, pass book, wallet, 'history'] )
+    ///   6.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   7.   `[]` Clock info
+    ///   8.   `[]` Rent info
+    ///   9.   `[]` System program
+    ///   10.  `[writable]` Creator payout account, for each pass book creator
+    ///   11.  `[writable]` Creator payout token account, for each pass book creator
+    ///   12.  `[]`         Optional market config account with address as pda of (PDA ['passbook', program id, market authority, 'market'] )
+    ///   13.  `[writable]` Optional market place payout account
+    ///   14.  `[writable]` Optional market place payout token account
+    ///   15.  `[]`         Optional referral user wallet
+    ///   16.  `[writable]` Optional referral payout account
+    ///   17.  `[writable]` Optional referral payout token account
+    ///   18.  `[]` SPL Token Program
+    ///   19.  `[]`         Program authority with address as pda of (PDA ['passbook', program id] ), only with a master edition
+    ///   20.  `[writable]` Vault token account holding the master edition, associated token account of the program authority
+    ///   21.  `[writable]` Master edition account
+    ///   22.  `[]`         Master metadata account
+    ///   23.  `[writable]` Edition marker account || Will be created by mpl_token_metadata
+    ///   24.  `[writable]` New mint account, the user wallet is its mint authority and holds its single token
+    ///   25.  `[writable]` New metadata account || Will be created by mpl_token_metadata
+    ///   26.  `[writable]` New edition account || Will be created by mpl_token_metadata
+    ///   27.  `[writable]` Pass account with address as pda of (PDA ['passbook', program id, new mint, 'pass'] )
+    ///   28.  `[]` Token metadata program
+    ///   29.  `[writable]` Token account of the user holding the new mint token, frozen if the pass book is soulbound (the new mint then needs a freeze authority)
+    ///   30.  `[]`         Collection mint of the store, if the store has a collection
+    ///   31.  `[]`         Collection metadata account, if the store has a collection
+    ///   32.  `[]`         Collection master edition account, if the store has a collection
+    ///   33.  `[writable]` Pass book the membership referred to, if it moves over from another pass book
+    ///
+    /// A pass book holding revenue in escrow replaces the payout accounts 10 to 17 by:
+    ///   10.  `[writable]` Escrow account with address as pda of (PDA ['passbook', program id, pass book, 'escrow'] )
+    ///   11.  `[writable]` Escrow token account owned by the escrow account, the escrow account for native SOL
     BuyPass(BuyPassArgs),
-
+    /// Use Membership
+    ///
+    /// Consume uses of an active membership.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   1.  `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.  `[signer]`   Owner of the membership or use authority
+    ///   3.  `[]` Clock info
+    ///   4.  `[writable]` Optional use authority record with address as pda of (PDA ['passbook', program id, membership, use authority, 'user'] ) or store authority record with address as pda of (PDA ['passbook', program id, store, store authority, 'admin'] )
+    ///
+    /// Parameters:
+    /// - count u64
+    UseMembership(UseMembershipArgs),
+    /// Approve Use Authority
+    ///
+    /// Allow a delegated wallet to consume uses of a membership on behalf of the owner.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Use authority record with address as pda of (PDA ['passbook', program id, membership, use authority, 'user'] )
+    ///   1.  `[]` Membership account
+    ///   2.  `[signer]` Owner of the membership
+    ///   3.  `[]` Use authority wallet
+    ///   4.  `[signer]` payer
+    ///   5.  `[]` Rent info
+    ///   6.  `[]` System program
+    ///
+    /// Parameters:
+    /// - number_of_uses u64
+    ApproveUseAuthority(ApproveUseAuthorityArgs),
+    /// Revoke Use Authority
+    ///
+    /// Close a use authority record and return its lamports to the membership owner.
+    /// Records of a membership closed by SyncMembershipOwner are revoked by its previous owner.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Use authority record with address as pda of (PDA ['passbook', program id, membership, use authority, 'user'] )
+    ///   1.  `[]` Membership account
+    ///   2.  `[signer, writable]` Owner of the membership
+    ///   3.  `[]` Use authority wallet
+    ///   4.  `[]` Store of the membership, if the membership account is closed
+    RevokeUseAuthority,
+    /// Add Store Authority
+    ///
+    /// Register a wallet allowed to redeem memberships of the store, up to a quota of redemptions.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Store authority record with address as pda of (PDA ['passbook', program id, store, store authority, 'admin'] )
+    ///   1.  `[]` Store account
+    ///   2.  `[signer]` Authority of the store
+    ///   3.  `[]` Store authority wallet
+    ///   4.  `[signer]` payer
+    ///   5.  `[]` Rent info
+    ///   6.  `[]` System program
+    ///
+    /// Parameters:
+    /// - allowed_redemptions u64
+    AddStoreAuthority(AddStoreAuthorityArgs),
+    /// Top Up Store Authority
+    ///
+    /// Increase the redemption quota of a store authority.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Store authority record with address as pda of (PDA ['passbook', program id, store, store authority, 'admin'] )
+    ///   1.  `[]` Store account
+    ///   2.  `[signer]` Authority of the store
+    ///   3.  `[]` Store authority wallet
+    ///
+    /// Parameters:
+    /// - additional_redemptions u64
+    TopUpStoreAuthority(TopUpStoreAuthorityArgs),
+    /// Remove Store Authority
+    ///
+    /// Close a store authority record and return its lamports to the store authority.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Store authority record with address as pda of (PDA ['passbook', program id, store, store authority, 'admin'] )
+    ///   1.  `[]` Store account
+    ///   2.  `[signer, writable]` Authority of the store
+    ///   3.  `[]` Store authority wallet
+    RemoveStoreAuthority,
+    /// Renew Membership
+    ///
+    /// Pay the pass book price again to extend an active membership from its current expiry
+    /// and add the pass book max_uses on top of the remaining uses.
+    ///
+    /// Accounts:
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[signer]`   The wallet of the user renewing the membership
+    ///   3.   `[writable]` Token account owned by user wallet used for transfer
+    ///   4.   `[signer]`   The fee payer
+    ///   5.   `[writable]` Trade history with address as pda of (PDA ['passbook', program id, pass book, wallet, 'history'] )
+    ///   6.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   7.   `[]` Clock info
+    ///   8.   `[]` Rent info
+    ///   9.   `[]` System program
+    ///   10.  `[writable]` Creator payout account, for each pass book creator
+    ///   11.  `[writable]` Creator payout token account, for each pass book creator
+    ///   12.  `[signer]`   Optional market place authority
+    ///   13.  `[writable]` Optional market place payout account
+    ///   14.  `[writable]` Optional market place payout token account
+    ///   15.  `[]`         Optional referral user wallet
+    ///   16.  `[writable]` Optional referral payout account
+    ///   17.  `[writable]` Optional referral payout token account
+    ///   18.  `[]` SPL Token Program
+    ///   19.  `[writable]` Pass account of the membership, if it has a printed pass
+    ///
+    /// A pass book holding revenue in escrow replaces the payout accounts 10 to 17 by its escrow account and escrow token account.
+    RenewMembership(BuyPassArgs),
+    /// Expire Membership
+    ///
+    /// Permissionless crank that expires a membership past its expiry date or without uses left.
+    /// A membership with a subscription left to charge is ended by ChargeSubscription instead.
+    /// Pays the store expiry bounty to the caller when the store balance allows it.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   1.  `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.  `[writable]` Caller wallet receiving the bounty
+    ///   3.  `[]` Clock info
+    ///   4.  `[]` Rent info
+    ExpireMembership,
+    /// Set Expiry Bounty
+    ///
+    /// Set the bounty paid from the store balance for expiring memberships.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   1.  `[signer]` Authority of the store
+    ///
+    /// Parameters:
+    /// - bounty u64
+    SetExpiryBounty(SetExpiryBountyArgs),
+    /// Withdraw
+    ///
+    /// Withdraw funds collected by a payout account.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Payout account with address as pda of (PDA ['passbook', program id, authority, mint, 'payout'] )
+    ///   1.  `[writable]` Treasury holder of the payout account
+    ///   2.  `[signer]` Authority of the payout account
+    ///   3.  `[writable]` Destination wallet for native mint or token account for spl mint
+    ///   4.  `[]` Rent info
+    ///   5.  `[]` SPL Token program, only for spl mint
+    ///
+    /// Parameters:
+    /// - amount u64
+    Withdraw(WithdrawArgs),
+    /// SetMarketConfig
+    ///
+    /// Create or update the fees charged by a market place on the pass books it sells.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Market config account with address as pda of (PDA ['passbook', program id, market authority, 'market'] )
+    ///   1.  `[signer]` Market place authority
+    ///   2.  `[signer]` Payer
+    ///   3.  `[]` Rent info
+    ///   4.  `[]` System program
+    ///
+    /// Parameters:
+    /// - market_fee_basis_point u16
+    /// - referral_share u8
+    /// - referral_kick_back_share u8
+    SetMarketConfig(SetMarketConfigArgs),
+    /// SyncMembershipOwner
+    ///
+    /// Permissionless crank moving a membership to the current holder of its pass edition,
+    /// so access follows the pass NFT on secondary sales.
+    /// A soulbound pass thawed for its transfer is frozen again, the holder then signs.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` Pass account with address as pda of (PDA ['passbook', program id, pass mint, 'pass'] )
+    ///   1.  `[writable]` Token account of the holder with the pass edition
+    ///   2.  `[]` Holder wallet, signer if the pass book is soulbound
+    ///   3.  `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   4.  `[writable]` Membership of the previous owner with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   5.  `[writable]` Membership of the holder with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   6.  `[writable]` Previous owner wallet receiving the rent of its closed membership
+    ///   7.  `[signer]` Payer
+    ///   8.  `[]` Clock info
+    ///   9.  `[]` Rent info
+    ///   10. `[]` System program
+    ///   11. `[]` Pass book account of the pass
+    ///   12. `[]` Program authority with address as pda of (PDA ['passbook', program id] ), if the pass book is soulbound
+    ///   13. `[]` Pass mint, if the pass book is soulbound
+    ///   14. `[]` Pass edition account, if the pass book is soulbound
+    ///   15. `[]` SPL Token program, if the pass book is soulbound
+    ///   16. `[]` Token metadata program, if the pass book is soulbound
+    ///   17. `[writable]` Pass book the replaced holder membership referred to, if any
+    SyncMembershipOwner,
+    /// ThawPass
+    ///
+    /// Thaw a soulbound pass so its holder can transfer it.
+    /// Once the pass book is deleted the holder thaws the pass itself.
+    ///
+    /// Accounts:
+    ///   0.  `[]` Pass account with address as pda of (PDA ['passbook', program id, pass mint, 'pass'] )
+    ///   1.  `[]` Pass book account of the pass
+    ///   2.  `[signer]` Authority of the pass book store, or the pass holder if the pass book is deleted
+    ///   3.  `[writable]` Frozen token account holding the pass
+    ///   4.  `[]` Pass mint
+    ///   5.  `[]` Pass edition account
+    ///   6.  `[]` Program authority with address as pda of (PDA ['passbook', program id] )
+    ///   7.  `[]` SPL Token program
+    ///   8.  `[]` Token metadata program
+    ThawPass,
+    /// CreateStoreCollection
+    ///
+    /// Mint the collection NFT of a store, held and updated by the program authority.
+    /// Passes printed afterwards are verified in this collection.
+    ///
+    /// Accounts:
+    ///   0.  `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   1.  `[signer]` Authority of the store
+    ///   2.  `[signer]` Payer
+    ///   3.  `[writable]` Collection mint with address as pda of (PDA ['passbook', program id, store, 'mint'] )
+    ///   4.  `[writable]` Collection token account, associated token account of the program authority
+    ///   5.  `[writable]` Collection metadata account
+    ///   6.  `[writable]` Collection master edition account
+    ///   7.  `[]` Program authority with address as pda of (PDA ['passbook', program id] )
+    ///   8.  `[]` Rent info
+    ///   9.  `[]` System program
+    ///   10. `[]` SPL Token program
+    ///   11. `[]` Associated token account program
+    ///   12. `[]` Token metadata program
+    ///
+    /// Parameters:
+    /// - name String
+    /// - symbol String
+    /// - uri String
+    CreateStoreCollection(CreateStoreCollectionArgs),
+    /// BuyGiftPass
+    ///
+    /// Buy a pass for another wallet.
+    /// The user wallet pays and keeps the trade history, the beneficiary gets the membership and the printed pass.
+    /// The wallet limit and the allowlist apply to the paying wallet: its gifts count towards its own limit,
+    /// the allowlist proof is for the paying wallet and the beneficiary needs neither.
+    ///
+    /// Accounts:
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[signer]`   The wallet of the user paying for the gift
+    ///   3.   `[writable]` Token account owned by user wallet used for transfer
+    ///   4.   `[signer]`   The fee payer
+    ///   5.   `[writable]` Trade history with address as pda of (PDA ['passbook', program id, pass book, user wallet, 'history'] )
+    ///   6.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, beneficiary wallet, 'membership'] )
+    ///   7.   `[]`         The wallet of the beneficiary, signing only to receive a soulbound pass
+    ///   8.   Same accounts as `BuyPass` from 7 onwards, the token account holding the new mint token is owned by the beneficiary
+    ///
+    /// Parameters:
+    /// - expected_price u64
+    /// - expected_mint Pubkey
+    /// - allowlist_proof Option<AllowlistProof>
+    BuyGiftPass(BuyPassArgs),
+    /// RefundMembership
+    ///
+    /// Cancel a purchase within the refund window of the pass book, before any use is consumed.
+    /// The payout accounts give back what the purchase paid them, in the mint it was paid in,
+    /// and the membership expires.
+    ///
+    /// Accounts:
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, member wallet, 'membership'] )
+    ///   3.   `[signer]`   The wallet of the member
+    ///   4.   `[writable]` Trade history of the buyer with address as pda of (PDA ['passbook', program id, pass book, buyer wallet, 'history'] )
+    ///   5.   `[writable]` Token account of the buyer receiving the refund, the buyer wallet for native SOL
+    ///   6.   `[]` Clock info
+    ///   7.   `[]` Rent info
+    ///   8.   `[]` SPL Token program
+    ///   9.   `[]` System program
+    ///   10.  `[writable]` Payout account and payout token account, for each payout recorded by the purchase in order
+    ///
+    /// A pass book holding revenue in escrow takes the refund from the escrow, the payout accounts are replaced by:
+    ///   10.  `[writable]` Escrow account with address as pda of (PDA ['passbook', program id, pass book, 'escrow'] )
+    ///   11.  `[writable]` Escrow token account owned by the escrow account, the escrow account for native SOL
+    RefundMembership,
+    /// SettleEscrow
+    ///
+    /// Pay the revenue held in escrow for the whole hold period to the payout accounts, later purchases stay held.
+    /// Anyone can settle, the revenue is split between creators, market place and referrer as on purchase
+    /// and the rounding dust goes to the first payout account.
+    ///
+    /// Accounts:
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[writable]` Escrow account with address as pda of (PDA ['passbook', program id, pass book, 'escrow'] )
+    ///   3.   `[writable]` Escrow token account owned by the escrow account, the escrow account for native SOL
+    ///   4.   `[]` Clock info
+    ///   5.   `[]` SPL Token program
+    ///   6.   `[]` System program
+    ///   7.   `[writable]` Creator payout account and payout token account, for each pass book creator
+    ///   8.   `[]`         Optional market config account with address as pda of (PDA ['passbook', program id, market authority, 'market'] )
+    ///   9.   `[writable]` Optional market place payout account
+    ///   10.  `[writable]` Optional market place payout token account
+    ///   11.  `[]`         Optional referral user wallet
+    ///   12.  `[writable]` Optional referral payout account
+    ///   13.  `[writable]` Optional referral payout token account
+    SettleEscrow,
+    /// SubscribeMembership
+    ///
+    /// Approve the membership as delegate of the member token account for the price of the given number of periods.
+    /// The membership is then renewed by `ChargeSubscription` each time it expires.
+    ///
+    /// Accounts:
+    ///   0.   `[]` Pass book account
+    ///   1.   `[]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   3.   `[signer]` The wallet of the member
+    ///   4.   `[writable]` Token account owned by the member wallet paying the subscription
+    ///   5.   `[]` Clock info
+    ///   6.   `[]` SPL Token program
+    SubscribeMembership(SubscribeArgs),
+    /// ChargeSubscription
+    ///
+    /// Charge the pass book price through the membership delegate once the membership expires and renew it.
+    /// Anyone can charge, the membership lapses if the price can't be charged.
+    ///
+    /// Accounts:
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[writable]` The pass store account with address as pda of (PDA ['passbook', program id, authority, 'store'] )
+    ///   2.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   3.   `[]` The wallet of the member
+    ///   4.   `[writable]` Token account of the subscription
+    ///   5.   `[signer]`   The fee payer
+    ///   6.   `[writable]` Trade history with address as pda of (PDA ['passbook', program id, pass book, wallet, 'history'] )
+    ///   7.   `[]` Clock info
+    ///   8.   `[]` Rent info
+    ///   9.   `[]` System program
+    ///   10.  `[writable]` Creator payout account, for each pass book creator
+    ///   11.  `[writable]` Creator payout token account, for each pass book creator
+    ///   12.  `[]`         Optional market config account with address as pda of (PDA ['passbook', program id, market authority, 'market'] )
+    ///   13.  `[writable]` Optional market place payout account
+    ///   14.  `[writable]` Optional market place payout token account
+    ///   15.  `[]`         Optional referral user wallet
+    ///   16.  `[writable]` Optional referral payout account
+    ///   17.  `[writable]` Optional referral payout token account
+    ///   18.  `[]` SPL Token Program
+    ///   19.  `[writable]` Pass account of the membership, if it has a printed pass
+    ///
+    /// A pass book holding revenue in escrow replaces the payout accounts 10 to 17 by its escrow account and escrow token account.
+    ChargeSubscription,
+    /// PauseMembership
+    ///
+    /// Stop the clock of an active membership, it can't be used until resumed.
+    /// Pauses since the last purchase or renewal add up to at most the pass book max_pause_days,
+    /// the membership resumes on its own once they are used up.
+    ///
+    /// Accounts:
+    ///   0.   `[]` Pass book account
+    ///   1.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   2.   `[signer]` The wallet of the member
+    ///   3.   `[]` Clock info
+    ///   4.   `[writable]` Pass account of the membership, if it has a printed pass
+    PauseMembership,
+    /// ResumeMembership
+    ///
+    /// Restart the clock of a paused membership, its expiry is pushed back by the paused duration.
+    /// A membership paused for longer than its pause allowance was resumed at the end of it.
+    ///
+    /// Accounts:
+    ///   0.   `[writable]` Pass book account
+    ///   1.   `[writable]` Membership account with address as pda of (PDA ['passbook', program id, store, wallet, 'membership'] )
+    ///   2.   `[signer]` The wallet of the member
+    ///   3.   `[]` Clock info
+    ///   4.   `[writable]` Pass account of the membership, if it has a printed pass
+    ResumeMembership,
 }
 
 /// Create `ActivatePassBook` instruction
@@ -212,28 +783,88 @@ pub fn delete_pass_book(
     passbook: &Pubkey,
     authority: &Pubkey,
     refunder: &Pubkey,
+    master_edition: Option<&MasterEditionArgs>,
+    refunds: Option<&PassBookRefundArgs>,
 ) -> Instruction {
-    let accounts = vec![
+    let (store, _) = find_pass_store_program_address(program_id, authority);
+    let mut accounts = vec![
         AccountMeta::new(*passbook, false),
         AccountMeta::new_readonly(*authority, true),
         AccountMeta::new(*refunder, false),
+        AccountMeta::new(store, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
     ];
-    Instruction::new_with_borsh(*program_id, &NFTPassInstruction::DeletePassBook, accounts)
+
+    if let Some(master_edition) = master_edition {
+        let (program_authority, _) = find_program_authority(program_id);
+        accounts.push(AccountMeta::new(
+            get_associated_token_address(&program_authority, &master_edition.mint),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(master_edition.mint, false));
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+        accounts.push(AccountMeta::new(master_edition.token_account, false));
+        accounts.push(AccountMeta::new_readonly(program_authority, false));
+        accounts.push(AccountMeta::new(
+            find_metadata_account(&master_edition.mint).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(mpl_token_metadata::id(), false));
+    }
+
+    if let Some(refunds) = refunds {
+        accounts.push(AccountMeta::new(refunds.source_token_account, false));
+        accounts.push(AccountMeta::new_readonly(refunds.token_program, false));
+        for member in &refunds.members {
+            accounts.push(AccountMeta::new(member.membership, false));
+            accounts.push(AccountMeta::new(member.token_account, false));
+        }
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::DeletePassBook(DeletePassBookArgs {
+            force: refunds.is_some(),
+        }),
+        accounts,
+    )
 }
 
 /// Create `EditPassBook` instruction
+#[allow(clippy::too_many_arguments)]
 pub fn edit_pass_book(
     program_id: &Pubkey,
     passbook: &Pubkey,
     authority: &Pubkey,
+    payer: &Pubkey,
+    master_edition_mint: Option<&Pubkey>,
+    creator_payouts: &[PayoutInfoArgs],
     mint: Option<&Pubkey>,
     args: EditPassBookArgs,
 ) -> Instruction {
     let mut accounts = vec![
         AccountMeta::new(*passbook, false),
         AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
     ];
 
+    if let Some(master_edition_mint) = master_edition_mint {
+        if args.name.is_some() || args.uri.is_some() {
+            let (metadata, _) = find_metadata_account(master_edition_mint);
+            let (program_authority, _) = find_program_authority(program_id);
+            accounts.push(AccountMeta::new(metadata, false));
+            accounts.push(AccountMeta::new_readonly(program_authority, false));
+            accounts.push(AccountMeta::new_readonly(mpl_token_metadata::id(), false));
+        }
+    }
+
+    for creator_payout in creator_payouts {
+        accounts.push(AccountMeta::new(creator_payout.payout_account, false));
+        accounts.push(AccountMeta::new(creator_payout.token_account, false));
+    }
+
     if let Some(new_price_mint) = mint {
         accounts.push(AccountMeta::new_readonly(*new_price_mint, false))
     }
@@ -254,13 +885,14 @@ pub fn init_pass_book(
     authority: &Pubkey,
     payer: &Pubkey,
     mint: &Pubkey,
-    creator_payout: &PayoutInfoArgs,
+    master_edition: Option<&MasterEditionArgs>,
+    creator_payouts: &[PayoutInfoArgs],
     market_payout: Option<&PayoutInfoArgs>,
     referral_payout: Option<&PayoutInfoArgs>,
     args: InitPassBookArgs,
 ) -> Instruction {
     let mut accounts = vec![
-        AccountMeta::new(*passbook, true),
+        AccountMeta::new(*passbook, master_edition.is_none()),
         AccountMeta::new(*store, false),
         AccountMeta::new_readonly(*authority, true),
         AccountMeta::new_readonly(*payer, true),
@@ -268,10 +900,37 @@ pub fn init_pass_book(
         AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
-        AccountMeta::new(creator_payout.payout_account, false),
-        AccountMeta::new(creator_payout.token_account, false),
     ];
 
+    if let Some(master_edition) = master_edition {
+        let (program_authority, _) = find_program_authority(program_id);
+        accounts.push(AccountMeta::new(master_edition.token_account, false));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address(&program_authority, &master_edition.mint),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(program_authority, false));
+        accounts.push(AccountMeta::new_readonly(master_edition.mint, false));
+        accounts.push(AccountMeta::new_readonly(
+            find_metadata_account(&master_edition.mint).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_master_edition_account(&master_edition.mint).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+        accounts.push(AccountMeta::new_readonly(
+            spl_associated_token_account::id(),
+            false,
+        ));
+    }
+
+    for creator_payout in creator_payouts {
+        accounts.push(AccountMeta::new(creator_payout.payout_account, false));
+        accounts.push(AccountMeta::new(creator_payout.token_account, false));
+    }
+
     if let Some(market_place) = market_payout {
         accounts.push(AccountMeta::new_readonly(market_place.authority, true));
         accounts.push(AccountMeta::new(market_place.payout_account, false));
@@ -291,7 +950,7 @@ pub fn init_pass_book(
 }
 
 
-/// Create `InitPassBook` instruction
+/// Create `BuyPass` instruction
 pub fn buy_pass(
     program_id: &Pubkey,
     passbook: &Pubkey,
@@ -301,42 +960,828 @@ pub fn buy_pass(
     payer: &Pubkey,
     trade_history: &Pubkey,
     membership: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
+    market_authority: Option<&PayoutInfoArgs>,
+    referral_authority: Option<&PayoutInfoArgs>,
+    creator_payouts: &[PayoutInfoArgs],
+    pass_mint: Option<&PassMintArgs>,
+    previous_passbook: Option<&Pubkey>,
+    args: BuyPassArgs,
+) -> Instruction {
+    let mut accounts = purchase_accounts(
+        program_id,
+        passbook,
+        store,
+        user_wallet,
+        user_token_account,
+        payer,
+        trade_history,
+        membership,
+        escrow_token_account,
+        market_authority,
+        referral_authority,
+        creator_payouts,
+    );
+
+    if let Some(pass_mint) = pass_mint {
+        accounts.extend(print_pass_accounts(program_id, pass_mint));
+    }
+    if let Some(previous_passbook) = previous_passbook {
+        accounts.push(AccountMeta::new(*previous_passbook, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::BuyPass(args),
+        accounts,
+    )
+}
+
+/// Create `BuyGiftPass` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn buy_gift_pass(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    user_token_account: &Pubkey,
+    payer: &Pubkey,
+    trade_history: &Pubkey,
+    membership: &Pubkey,
+    beneficiary: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
     market_authority: Option<&PayoutInfoArgs>,
     referral_authority: Option<&PayoutInfoArgs>,
-    creator_payout: &PayoutInfoArgs,
+    creator_payouts: &[PayoutInfoArgs],
+    pass_mint: Option<&PassMintArgs>,
+    previous_passbook: Option<&Pubkey>,
     args: BuyPassArgs,
+) -> Instruction {
+    let mut accounts = purchase_accounts(
+        program_id,
+        passbook,
+        store,
+        user_wallet,
+        user_token_account,
+        payer,
+        trade_history,
+        membership,
+        escrow_token_account,
+        market_authority,
+        referral_authority,
+        creator_payouts,
+    );
+    // the beneficiary follows the membership account
+    accounts.insert(7, AccountMeta::new_readonly(*beneficiary, false));
+
+    if let Some(pass_mint) = pass_mint {
+        accounts.extend(print_pass_accounts(program_id, pass_mint));
+    }
+    if let Some(previous_passbook) = previous_passbook {
+        accounts.push(AccountMeta::new(*previous_passbook, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::BuyGiftPass(args),
+        accounts,
+    )
+}
+
+/// Create `RefundMembership` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn refund_membership(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    membership: &Pubkey,
+    member_wallet: &Pubkey,
+    trade_history: &Pubkey,
+    buyer_token_account: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
+    purchase_payouts: &[PayoutInfoArgs],
 ) -> Instruction {
     let mut accounts = vec![
         AccountMeta::new(*passbook, false),
         AccountMeta::new(*store, false),
-        AccountMeta::new(*user_wallet, true),
-        AccountMeta::new(*user_token_account, false),
-        AccountMeta::new(*payer, true),
-        AccountMeta::new(*trade_history, false),
         AccountMeta::new(*membership, false),
+        AccountMeta::new_readonly(*member_wallet, true),
+        AccountMeta::new(*trade_history, false),
+        AccountMeta::new(*buyer_token_account, false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
-        AccountMeta::new_readonly(system_program::id(), false),    
-        AccountMeta::new(creator_payout.payout_account, false),  
-        AccountMeta::new(creator_payout.token_account, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
     ];
-
-    if let Some(market_place) = market_authority {
-        accounts.push(AccountMeta::new_readonly(market_place.authority, true));
-        accounts.push(AccountMeta::new(market_place.payout_account, false));
-        accounts.push(AccountMeta::new(market_place.token_account, false))
+    if let Some(escrow_token_account) = escrow_token_account {
+        let (escrow, _) = find_pass_book_escrow(program_id, passbook);
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(*escrow_token_account, false));
     }
 
-    if let Some(referral) = referral_authority {
-        accounts.push(AccountMeta::new_readonly(referral.authority, false));
-        accounts.push(AccountMeta::new(referral.payout_account, false));
-        accounts.push(AccountMeta::new(referral.token_account, false))
+    for purchase_payout in purchase_payouts {
+        accounts.push(AccountMeta::new(purchase_payout.payout_account, false));
+        accounts.push(AccountMeta::new(purchase_payout.token_account, false));
     }
 
-    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
     Instruction::new_with_borsh(
         *program_id,
-        &NFTPassInstruction::BuyPass(args),
+        &NFTPassInstruction::RefundMembership,
+        accounts,
+    )
+}
+
+/// Create `SettleEscrow` instruction
+pub fn settle_escrow(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    escrow_token_account: &Pubkey,
+    market_authority: Option<&PayoutInfoArgs>,
+    referral_authority: Option<&PayoutInfoArgs>,
+    creator_payouts: &[PayoutInfoArgs],
+) -> Instruction {
+    let (escrow, _) = find_pass_book_escrow(program_id, passbook);
+    let mut accounts = vec![
+        AccountMeta::new(*passbook, false),
+        AccountMeta::new_readonly(*store, false),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new(*escrow_token_account, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(payout_accounts(
+        program_id,
+        passbook,
+        None,
+        market_authority,
+        referral_authority,
+        creator_payouts,
+    ));
+
+    Instruction::new_with_borsh(*program_id, &NFTPassInstruction::SettleEscrow, accounts)
+}
+
+/// Create `SubscribeMembership` instruction
+pub fn subscribe_membership(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    user_token_account: &Pubkey,
+    args: SubscribeArgs,
+) -> Instruction {
+    let (membership, _) = find_membership_program_address(program_id, store, user_wallet);
+    let accounts = vec![
+        AccountMeta::new_readonly(*passbook, false),
+        AccountMeta::new_readonly(*store, false),
+        AccountMeta::new(membership, false),
+        AccountMeta::new_readonly(*user_wallet, true),
+        AccountMeta::new(*user_token_account, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::SubscribeMembership(args),
         accounts,
     )
-}
\ No newline at end of file
+}
+
+/// Create `ChargeSubscription` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn charge_subscription(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    user_token_account: &Pubkey,
+    payer: &Pubkey,
+    trade_history: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
+    market_authority: Option<&PayoutInfoArgs>,
+    referral_authority: Option<&PayoutInfoArgs>,
+    creator_payouts: &[PayoutInfoArgs],
+    pass_mint: Option<&Pubkey>,
+) -> Instruction {
+    let (membership, _) = find_membership_program_address(program_id, store, user_wallet);
+    let mut accounts = vec![
+        AccountMeta::new(*passbook, false),
+        AccountMeta::new(*store, false),
+        AccountMeta::new(membership, false),
+        AccountMeta::new_readonly(*user_wallet, false),
+        AccountMeta::new(*user_token_account, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*trade_history, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(payout_accounts(
+        program_id,
+        passbook,
+        escrow_token_account,
+        market_authority,
+        referral_authority,
+        creator_payouts,
+    ));
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    if let Some(pass_mint) = pass_mint {
+        let (pass, _) = find_pass_program_address(program_id, pass_mint);
+        accounts.push(AccountMeta::new(pass, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::ChargeSubscription,
+        accounts,
+    )
+}
+
+/// Create `PauseMembership` instruction
+pub fn pause_membership(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    pass_mint: Option<&Pubkey>,
+) -> Instruction {
+    let (membership, _) = find_membership_program_address(program_id, store, user_wallet);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*passbook, false),
+        AccountMeta::new(membership, false),
+        AccountMeta::new_readonly(*user_wallet, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(pass_mint) = pass_mint {
+        let (pass, _) = find_pass_program_address(program_id, pass_mint);
+        accounts.push(AccountMeta::new(pass, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::PauseMembership,
+        accounts,
+    )
+}
+
+/// Create `ResumeMembership` instruction
+pub fn resume_membership(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    pass_mint: Option<&Pubkey>,
+) -> Instruction {
+    let (membership, _) = find_membership_program_address(program_id, store, user_wallet);
+    let mut accounts = vec![
+        AccountMeta::new(*passbook, false),
+        AccountMeta::new(membership, false),
+        AccountMeta::new_readonly(*user_wallet, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(pass_mint) = pass_mint {
+        let (pass, _) = find_pass_program_address(program_id, pass_mint);
+        accounts.push(AccountMeta::new(pass, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::ResumeMembership,
+        accounts,
+    )
+}
+
+/// Create `RenewMembership` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn renew_membership(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    user_token_account: &Pubkey,
+    payer: &Pubkey,
+    trade_history: &Pubkey,
+    membership: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
+    market_authority: Option<&PayoutInfoArgs>,
+    referral_authority: Option<&PayoutInfoArgs>,
+    creator_payouts: &[PayoutInfoArgs],
+    pass_mint: Option<&Pubkey>,
+    args: BuyPassArgs,
+) -> Instruction {
+    let mut accounts = purchase_accounts(
+        program_id,
+        passbook,
+        store,
+        user_wallet,
+        user_token_account,
+        payer,
+        trade_history,
+        membership,
+        escrow_token_account,
+        market_authority,
+        referral_authority,
+        creator_payouts,
+    );
+    if let Some(pass_mint) = pass_mint {
+        let (pass, _) = find_pass_program_address(program_id, pass_mint);
+        accounts.push(AccountMeta::new(pass, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::RenewMembership(args),
+        accounts,
+    )
+}
+
+/// Accounts shared by `BuyPass` and `RenewMembership` instructions
+#[allow(clippy::too_many_arguments)]
+fn purchase_accounts(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    store: &Pubkey,
+    user_wallet: &Pubkey,
+    user_token_account: &Pubkey,
+    payer: &Pubkey,
+    trade_history: &Pubkey,
+    membership: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
+    market_authority: Option<&PayoutInfoArgs>,
+    referral_authority: Option<&PayoutInfoArgs>,
+    creator_payouts: &[PayoutInfoArgs],
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*passbook, false),
+        AccountMeta::new(*store, false),
+        AccountMeta::new(*user_wallet, true),
+        AccountMeta::new(*user_token_account, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*trade_history, false),
+        AccountMeta::new(*membership, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    accounts.extend(payout_accounts(
+        program_id,
+        passbook,
+        escrow_token_account,
+        market_authority,
+        referral_authority,
+        creator_payouts,
+    ));
+
+    accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    accounts
+}
+
+/// Payout accounts sharing the price of a purchase, in the order they are paid,
+/// or the escrow of the pass book and its token account when it holds the revenue
+fn payout_accounts(
+    program_id: &Pubkey,
+    passbook: &Pubkey,
+    escrow_token_account: Option<&Pubkey>,
+    market_authority: Option<&PayoutInfoArgs>,
+    referral_authority: Option<&PayoutInfoArgs>,
+    creator_payouts: &[PayoutInfoArgs],
+) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+
+    if let Some(escrow_token_account) = escrow_token_account {
+        let (escrow, _) = find_pass_book_escrow(program_id, passbook);
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(*escrow_token_account, false));
+        return accounts;
+    }
+
+    for creator_payout in creator_payouts {
+        accounts.push(AccountMeta::new(creator_payout.payout_account, false));
+        accounts.push(AccountMeta::new(creator_payout.token_account, false));
+    }
+
+    if let Some(market_place) = market_authority {
+        let (market_config, _) =
+            find_market_config_program_address(program_id, &market_place.authority);
+        accounts.push(AccountMeta::new_readonly(market_config, false));
+        accounts.push(AccountMeta::new(market_place.payout_account, false));
+        accounts.push(AccountMeta::new(market_place.token_account, false))
+    }
+
+    if let Some(referral) = referral_authority {
+        accounts.push(AccountMeta::new_readonly(referral.authority, false));
+        accounts.push(AccountMeta::new(referral.payout_account, false));
+        accounts.push(AccountMeta::new(referral.token_account, false))
+    }
+
+    accounts
+}
+
+/// Accounts needed by `BuyPass` to print a pass edition
+fn print_pass_accounts(program_id: &Pubkey, pass_mint: &PassMintArgs) -> Vec<AccountMeta> {
+    let (program_authority, _) = find_program_authority(program_id);
+    let edition_number = (pass_mint.edition / EDITION_MARKER_BIT_SIZE).to_string();
+    let (edition_marker, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            pass_mint.master_edition_mint.as_ref(),
+            EDITION.as_bytes(),
+            edition_number.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    );
+    let (pass, _) = find_pass_program_address(program_id, &pass_mint.mint);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(program_authority, false),
+        AccountMeta::new(
+            get_associated_token_address(&program_authority, &pass_mint.master_edition_mint),
+            false,
+        ),
+        AccountMeta::new(
+            find_master_edition_account(&pass_mint.master_edition_mint).0,
+            false,
+        ),
+        AccountMeta::new_readonly(find_metadata_account(&pass_mint.master_edition_mint).0, false),
+        AccountMeta::new(edition_marker, false),
+        AccountMeta::new(pass_mint.mint, false),
+        AccountMeta::new(find_metadata_account(&pass_mint.mint).0, false),
+        AccountMeta::new(find_master_edition_account(&pass_mint.mint).0, false),
+        AccountMeta::new(pass, false),
+        AccountMeta::new_readonly(mpl_token_metadata::id(), false),
+        AccountMeta::new(pass_mint.token_account, false),
+    ];
+
+    if let Some(collection_mint) = pass_mint.collection_mint {
+        accounts.push(AccountMeta::new_readonly(collection_mint, false));
+        accounts.push(AccountMeta::new_readonly(
+            find_metadata_account(&collection_mint).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_master_edition_account(&collection_mint).0,
+            false,
+        ));
+    }
+
+    accounts
+}
+
+/// Create `UseMembership` instruction
+pub fn use_membership(
+    program_id: &Pubkey,
+    membership: &Pubkey,
+    store: &Pubkey,
+    authority: &Pubkey,
+    use_authority_record: Option<&Pubkey>,
+    args: UseMembershipArgs,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*membership, false),
+        AccountMeta::new(*store, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    if let Some(record) = use_authority_record {
+        accounts.push(AccountMeta::new(*record, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::UseMembership(args),
+        accounts,
+    )
+}
+
+/// Create `ApproveUseAuthority` instruction
+pub fn approve_use_authority(
+    program_id: &Pubkey,
+    use_authority_record: &Pubkey,
+    membership: &Pubkey,
+    owner: &Pubkey,
+    user: &Pubkey,
+    payer: &Pubkey,
+    args: ApproveUseAuthorityArgs,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*use_authority_record, false),
+        AccountMeta::new_readonly(*membership, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*user, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::ApproveUseAuthority(args),
+        accounts,
+    )
+}
+
+/// Create `RevokeUseAuthority` instruction
+pub fn revoke_use_authority(
+    program_id: &Pubkey,
+    use_authority_record: &Pubkey,
+    membership: &Pubkey,
+    owner: &Pubkey,
+    user: &Pubkey,
+    closed_membership_store: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*use_authority_record, false),
+        AccountMeta::new_readonly(*membership, false),
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(*user, false),
+    ];
+    if let Some(store) = closed_membership_store {
+        accounts.push(AccountMeta::new_readonly(*store, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::RevokeUseAuthority,
+        accounts,
+    )
+}
+
+/// Create `AddStoreAuthority` instruction
+pub fn add_store_authority(
+    program_id: &Pubkey,
+    store_authority_record: &Pubkey,
+    store: &Pubkey,
+    authority: &Pubkey,
+    user: &Pubkey,
+    payer: &Pubkey,
+    args: AddStoreAuthorityArgs,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*store_authority_record, false),
+        AccountMeta::new_readonly(*store, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*user, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::AddStoreAuthority(args),
+        accounts,
+    )
+}
+
+/// Create `TopUpStoreAuthority` instruction
+pub fn top_up_store_authority(
+    program_id: &Pubkey,
+    store_authority_record: &Pubkey,
+    store: &Pubkey,
+    authority: &Pubkey,
+    user: &Pubkey,
+    args: TopUpStoreAuthorityArgs,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*store_authority_record, false),
+        AccountMeta::new_readonly(*store, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*user, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::TopUpStoreAuthority(args),
+        accounts,
+    )
+}
+
+/// Create `RemoveStoreAuthority` instruction
+pub fn remove_store_authority(
+    program_id: &Pubkey,
+    store_authority_record: &Pubkey,
+    store: &Pubkey,
+    authority: &Pubkey,
+    user: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*store_authority_record, false),
+        AccountMeta::new_readonly(*store, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(*user, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::RemoveStoreAuthority,
+        accounts,
+    )
+}
+
+/// Create `ExpireMembership` instruction
+pub fn expire_membership(
+    program_id: &Pubkey,
+    membership: &Pubkey,
+    store: &Pubkey,
+    caller: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*membership, false),
+        AccountMeta::new(*store, false),
+        AccountMeta::new(*caller, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::ExpireMembership,
+        accounts,
+    )
+}
+
+/// Create `SetExpiryBounty` instruction
+pub fn set_expiry_bounty(
+    program_id: &Pubkey,
+    store: &Pubkey,
+    authority: &Pubkey,
+    args: SetExpiryBountyArgs,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*store, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::SetExpiryBounty(args),
+        accounts,
+    )
+}
+
+/// Create `Withdraw` instruction
+pub fn withdraw(
+    program_id: &Pubkey,
+    payout: &Pubkey,
+    treasury_holder: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+    is_native: bool,
+    args: WithdrawArgs,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payout, false),
+        AccountMeta::new(*treasury_holder, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    if !is_native {
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    }
+
+    Instruction::new_with_borsh(*program_id, &NFTPassInstruction::Withdraw(args), accounts)
+}
+
+/// Create `SetMarketConfig` instruction
+pub fn set_market_config(
+    program_id: &Pubkey,
+    market_config: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    args: SetMarketConfigArgs,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*market_config, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::SetMarketConfig(args),
+        accounts,
+    )
+}
+
+/// Create `SyncMembershipOwner` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn sync_membership_owner(
+    program_id: &Pubkey,
+    pass_mint: &Pubkey,
+    token_account: &Pubkey,
+    holder: &Pubkey,
+    previous_owner: &Pubkey,
+    store: &Pubkey,
+    payer: &Pubkey,
+    passbook: &Pubkey,
+    soulbound: bool,
+    replaced_passbook: Option<&Pubkey>,
+) -> Instruction {
+    let (pass, _) = find_pass_program_address(program_id, pass_mint);
+    let (previous_membership, _) =
+        find_membership_program_address(program_id, store, previous_owner);
+    let (membership, _) = find_membership_program_address(program_id, store, holder);
+    let mut accounts = vec![
+        AccountMeta::new(pass, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new_readonly(*holder, soulbound),
+        AccountMeta::new(*store, false),
+        AccountMeta::new(previous_membership, false),
+        AccountMeta::new(membership, false),
+        AccountMeta::new(*previous_owner, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*passbook, false),
+    ];
+    if soulbound {
+        let (program_authority, _) = find_program_authority(program_id);
+        let (edition, _) = find_master_edition_account(pass_mint);
+        accounts.push(AccountMeta::new_readonly(program_authority, false));
+        accounts.push(AccountMeta::new_readonly(*pass_mint, false));
+        accounts.push(AccountMeta::new_readonly(edition, false));
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+        accounts.push(AccountMeta::new_readonly(mpl_token_metadata::id(), false));
+    }
+    if let Some(replaced_passbook) = replaced_passbook {
+        accounts.push(AccountMeta::new(*replaced_passbook, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::SyncMembershipOwner,
+        accounts,
+    )
+}
+
+/// Create `ThawPass` instruction
+pub fn thaw_pass(
+    program_id: &Pubkey,
+    pass_mint: &Pubkey,
+    passbook: &Pubkey,
+    authority: &Pubkey,
+    token_account: &Pubkey,
+) -> Instruction {
+    let (pass, _) = find_pass_program_address(program_id, pass_mint);
+    let (program_authority, _) = find_program_authority(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(pass, false),
+        AccountMeta::new_readonly(*passbook, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new_readonly(*pass_mint, false),
+        AccountMeta::new_readonly(find_master_edition_account(pass_mint).0, false),
+        AccountMeta::new_readonly(program_authority, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(mpl_token_metadata::id(), false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &NFTPassInstruction::ThawPass, accounts)
+}
+
+/// Create `CreateStoreCollection` instruction
+pub fn create_store_collection(
+    program_id: &Pubkey,
+    store: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    args: CreateStoreCollectionArgs,
+) -> Instruction {
+    let (collection_mint, _) = find_pass_collection_mint(program_id, store);
+    let (program_authority, _) = find_program_authority(program_id);
+    let accounts = vec![
+        AccountMeta::new(*store, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(collection_mint, false),
+        AccountMeta::new(
+            get_associated_token_address(&program_authority, &collection_mint),
+            false,
+        ),
+        AccountMeta::new(find_metadata_account(&collection_mint).0, false),
+        AccountMeta::new(find_master_edition_account(&collection_mint).0, false),
+        AccountMeta::new_readonly(program_authority, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(mpl_token_metadata::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &NFTPassInstruction::CreateStoreCollection(args),
+        accounts,
+    )
+}
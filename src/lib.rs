@@ -1,5 +1,6 @@
 pub mod error;
 pub mod instruction;
+pub mod merkle;
 pub mod processor;
 pub mod state;
 pub mod utils;
@@ -16,23 +17,38 @@ pub mod entrypoint;
 pub use solana_program;
 use solana_program::pubkey::Pubkey;
 use state::{
-    Membership, Payout, Store, StoreAuthority, TradeHistory, UseAuthority, COLLECTION_MINT, PREFIX,
+    MarketConfig, Membership, Pass, Payout, Store, StoreAuthority, TradeHistory, UseAuthority,
+    COLLECTION_MINT, ESCROW, PREFIX,
 };
 
 solana_program::declare_id!("passjvPvHQWN4SvBCmHk1gdrtBvoHRERtQK9MKemreQ");
 
-/// Generates pass collection mint
-pub fn find_pass_collection_mint(program_id: &Pubkey) -> (Pubkey, u8) {
+/// Generates pass collection mint of a store
+pub fn find_pass_collection_mint(program_id: &Pubkey, store: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             PREFIX.as_bytes(),
             program_id.as_ref(),
+            &store.to_bytes(),
             COLLECTION_MINT.as_bytes(),
         ],
         program_id,
     )
 }
 
+/// Generates escrow address holding the revenue of a pass book
+pub fn find_pass_book_escrow(program_id: &Pubkey, pass_book: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &pass_book.to_bytes(),
+            ESCROW.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
 /// Generates program authority
 pub fn find_program_authority(program_id: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[PREFIX.as_bytes(), program_id.as_ref()], program_id)
@@ -95,6 +111,19 @@ pub fn find_pass_book_program_address(program_id: &Pubkey, mint: &Pubkey) -> (Pu
     )
 }
 
+/// Generates pass address of a printed edition
+pub fn find_pass_program_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &mint.to_bytes(),
+            Pass::PREFIX.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
 /// Generate membership pda
 pub fn find_membership_program_address(
     program_id: &Pubkey,
@@ -148,3 +177,16 @@ pub fn find_store_authority_program_address(
         program_id,
     )
 }
+
+/// Generate market config pda
+pub fn find_market_config_program_address(program_id: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &authority.to_bytes(),
+            MarketConfig::PREFIX.as_bytes(),
+        ],
+        program_id,
+    )
+}
@@ -0,0 +1,79 @@
+//! Merkle tree used to gate purchases behind an allowlist
+//!
+//! Leaves and nodes are hashed with distinct prefixes and node children are sorted
+//! before hashing, so a proof is just the list of sibling hashes.
+
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Hash of an allowlist entry, the allocation caps the passes the wallet can buy
+pub fn allowlist_leaf(wallet: &Pubkey, allocation: Option<u64>) -> [u8; 32] {
+    match allocation {
+        Some(allocation) => {
+            hashv(&[LEAF_PREFIX, wallet.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+        }
+        None => hashv(&[LEAF_PREFIX, wallet.as_ref()]).to_bytes(),
+    }
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    if left <= right {
+        hashv(&[NODE_PREFIX, left.as_ref(), right.as_ref()]).to_bytes()
+    } else {
+        hashv(&[NODE_PREFIX, right.as_ref(), left.as_ref()]).to_bytes()
+    }
+}
+
+/// Check the leaf belongs to the tree with the given root
+pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_node(&node, sibling));
+    computed == *root
+}
+
+/// Off-chain builder of allowlist trees and proofs
+#[derive(Debug, Clone)]
+pub struct AllowlistTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl AllowlistTree {
+    /// Build the tree from the leaves, an odd node is promoted to the next layer as is
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut layers = vec![leaves];
+        while layers[layers.len() - 1].len() > 1 {
+            let next = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    /// Root to store on the pass book, `None` for an empty tree
+    pub fn root(&self) -> Option<[u8; 32]> {
+        self.layers.last().and_then(|layer| layer.first().copied())
+    }
+
+    /// Proof of the leaf at the given index
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = vec![];
+        let mut index = index;
+        for layer in self.layers.iter().take(self.layers.len() - 1) {
+            let sibling = index ^ 1;
+            if sibling < layer.len() {
+                proof.push(layer[sibling]);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
@@ -6,7 +6,27 @@ use edit_pass_book::edit_pass_book;
 use delete_pass_book::delete_pass_book;
 use activate_pass_book::activate_pass_book;
 use deactivate_pass_book::deactivate_pass_book;
-use buy_pass_book::buy;
+use buy_pass_book::{buy, buy_gift};
+use use_membership::use_membership;
+use approve_use_authority::approve_use_authority;
+use revoke_use_authority::revoke_use_authority;
+use add_store_authority::add_store_authority;
+use top_up_store_authority::top_up_store_authority;
+use remove_store_authority::remove_store_authority;
+use renew_membership::renew_membership;
+use expire_membership::expire_membership;
+use set_expiry_bounty::set_expiry_bounty;
+use withdraw::withdraw;
+use set_market_config::set_market_config;
+use settle_escrow::settle_escrow;
+use subscribe_membership::subscribe_membership;
+use charge_subscription::charge_subscription;
+use pause_membership::pause_membership;
+use resume_membership::resume_membership;
+use sync_membership_owner::sync_membership_owner;
+use thaw_pass::thaw_pass;
+use create_store_collection::create_store_collection;
+use refund_membership::refund_membership;
 
 use borsh::BorshDeserialize;
 use crate::instruction::NFTPassInstruction;
@@ -19,6 +39,26 @@ pub mod delete_pass_book;
 pub mod activate_pass_book;
 pub mod deactivate_pass_book;
 pub mod buy_pass_book;
+pub mod use_membership;
+pub mod approve_use_authority;
+pub mod revoke_use_authority;
+pub mod add_store_authority;
+pub mod top_up_store_authority;
+pub mod remove_store_authority;
+pub mod renew_membership;
+pub mod expire_membership;
+pub mod set_expiry_bounty;
+pub mod withdraw;
+pub mod set_market_config;
+pub mod settle_escrow;
+pub mod subscribe_membership;
+pub mod charge_subscription;
+pub mod pause_membership;
+pub mod resume_membership;
+pub mod sync_membership_owner;
+pub mod thaw_pass;
+pub mod create_store_collection;
+pub mod refund_membership;
 
 pub struct Processor {}
 
@@ -40,9 +80,9 @@ impl Processor {
                 msg!("Instruction: DeactivatePassBook");
                 deactivate_pass_book(program_id, accounts)
             },
-            NFTPassInstruction::DeletePassBook => {
+            NFTPassInstruction::DeletePassBook(args) => {
                 msg!("Instruction: DeletePassBook");
-                delete_pass_book(program_id, accounts)
+                delete_pass_book(program_id, accounts, args)
             },
             NFTPassInstruction::EditPassBook(args) => {
                 msg!("Instruction: EditPassBook");
@@ -55,6 +95,90 @@ impl Processor {
             NFTPassInstruction::BuyPass(args) => {
                 msg!("Instruction: BuyPass");
                 buy(program_id, accounts, args)
+            },
+            NFTPassInstruction::UseMembership(args) => {
+                msg!("Instruction: UseMembership");
+                use_membership(program_id, accounts, args)
+            },
+            NFTPassInstruction::ApproveUseAuthority(args) => {
+                msg!("Instruction: ApproveUseAuthority");
+                approve_use_authority(program_id, accounts, args)
+            },
+            NFTPassInstruction::RevokeUseAuthority => {
+                msg!("Instruction: RevokeUseAuthority");
+                revoke_use_authority(program_id, accounts)
+            },
+            NFTPassInstruction::AddStoreAuthority(args) => {
+                msg!("Instruction: AddStoreAuthority");
+                add_store_authority(program_id, accounts, args)
+            },
+            NFTPassInstruction::TopUpStoreAuthority(args) => {
+                msg!("Instruction: TopUpStoreAuthority");
+                top_up_store_authority(program_id, accounts, args)
+            },
+            NFTPassInstruction::RemoveStoreAuthority => {
+                msg!("Instruction: RemoveStoreAuthority");
+                remove_store_authority(program_id, accounts)
+            },
+            NFTPassInstruction::RenewMembership(args) => {
+                msg!("Instruction: RenewMembership");
+                renew_membership(program_id, accounts, args)
+            },
+            NFTPassInstruction::ExpireMembership => {
+                msg!("Instruction: ExpireMembership");
+                expire_membership(program_id, accounts)
+            },
+            NFTPassInstruction::SetExpiryBounty(args) => {
+                msg!("Instruction: SetExpiryBounty");
+                set_expiry_bounty(program_id, accounts, args)
+            },
+            NFTPassInstruction::Withdraw(args) => {
+                msg!("Instruction: Withdraw");
+                withdraw(program_id, accounts, args)
+            },
+            NFTPassInstruction::SetMarketConfig(args) => {
+                msg!("Instruction: SetMarketConfig");
+                set_market_config(program_id, accounts, args)
+            },
+            NFTPassInstruction::SyncMembershipOwner => {
+                msg!("Instruction: SyncMembershipOwner");
+                sync_membership_owner(program_id, accounts)
+            },
+            NFTPassInstruction::ThawPass => {
+                msg!("Instruction: ThawPass");
+                thaw_pass(program_id, accounts)
+            },
+            NFTPassInstruction::CreateStoreCollection(args) => {
+                msg!("Instruction: CreateStoreCollection");
+                create_store_collection(program_id, accounts, args)
+            },
+            NFTPassInstruction::BuyGiftPass(args) => {
+                msg!("Instruction: BuyGiftPass");
+                buy_gift(program_id, accounts, args)
+            },
+            NFTPassInstruction::RefundMembership => {
+                msg!("Instruction: RefundMembership");
+                refund_membership(program_id, accounts)
+            }
+            NFTPassInstruction::SettleEscrow => {
+                msg!("Instruction: SettleEscrow");
+                settle_escrow(program_id, accounts)
+            },
+            NFTPassInstruction::SubscribeMembership(args) => {
+                msg!("Instruction: SubscribeMembership");
+                subscribe_membership(program_id, accounts, args)
+            },
+            NFTPassInstruction::ChargeSubscription => {
+                msg!("Instruction: ChargeSubscription");
+                charge_subscription(program_id, accounts)
+            },
+            NFTPassInstruction::PauseMembership => {
+                msg!("Instruction: PauseMembership");
+                pause_membership(program_id, accounts)
+            },
+            NFTPassInstruction::ResumeMembership => {
+                msg!("Instruction: ResumeMembership");
+                resume_membership(program_id, accounts)
             }
         }
     }
@@ -0,0 +1,89 @@
+//! AddStoreAuthority instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_pass_store_program_address, find_store_authority_program_address,
+    instruction::AddStoreAuthorityArgs,
+    state::{Store, StoreAuthority, PREFIX},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process AddStoreAuthority instruction
+pub fn add_store_authority<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: AddStoreAuthorityArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_authority_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(store_info, program_id)?;
+    assert_signer(authority_info)?;
+    assert_signer(payer_info)?;
+
+    if args.allowed_redemptions == 0 {
+        return Err(NFTPassError::WrongRedemptionCount.into());
+    }
+
+    // make sure the store is initialized
+    Store::unpack(&store_info.data.borrow_mut())?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, authority_info.key);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let (store_authority_key, store_authority_bump_seed) =
+        find_store_authority_program_address(program_id, store_info.key, user_info.key);
+    assert_account_key(
+        store_authority_info,
+        &store_authority_key,
+        Some(NFTPassError::InvalidStoreAuthorityKey),
+    )?;
+
+    if StoreAuthority::unpack(&store_authority_info.data.borrow_mut()).is_ok() {
+        return Err(NFTPassError::StoreAuthorityAlreadyExists.into());
+    }
+
+    let store_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &store_info.key.to_bytes(),
+        &user_info.key.to_bytes(),
+        StoreAuthority::PREFIX.as_bytes(),
+        &[store_authority_bump_seed],
+    ];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        store_authority_info,
+        rent_info,
+        system_account_info,
+        payer_info,
+        StoreAuthority::LEN,
+        store_authority_signer_seeds,
+    )?;
+
+    msg!("New store authority account was created");
+
+    let mut store_authority =
+        StoreAuthority::unpack_unchecked(&store_authority_info.data.borrow_mut())?;
+
+    store_authority.init(*store_info.key, store_authority_bump_seed);
+    store_authority.allowed_redemptions = args.allowed_redemptions;
+
+    StoreAuthority::pack(store_authority, *store_authority_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! ApproveUseAuthority instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_use_authority_program_address,
+    instruction::ApproveUseAuthorityArgs,
+    state::{Membership, UseAuthority, PREFIX},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process ApproveUseAuthority instruction
+pub fn approve_use_authority<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: ApproveUseAuthorityArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let use_authority_record_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(membership_info, program_id)?;
+    assert_signer(owner_info)?;
+    assert_signer(payer_info)?;
+
+    if args.number_of_uses == 0 {
+        return Err(NFTPassError::WrongUseCount.into());
+    }
+
+    let membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+
+    assert_account_key(
+        owner_info,
+        &membership.owner,
+        Some(NFTPassError::InvalidMembershipAuthority),
+    )?;
+
+    let (use_authority_key, use_authority_bump_seed) =
+        find_use_authority_program_address(program_id, membership_info.key, user_info.key);
+    assert_account_key(
+        use_authority_record_info,
+        &use_authority_key,
+        Some(NFTPassError::InvalidUseAuthorityKey),
+    )?;
+
+    let use_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &membership_info.key.to_bytes(),
+        &user_info.key.to_bytes(),
+        UseAuthority::PREFIX.as_bytes(),
+        &[use_authority_bump_seed],
+    ];
+
+    let mut use_authority = get_or_create_use_authority(
+        program_id,
+        use_authority_record_info,
+        membership_info,
+        payer_info,
+        rent_info,
+        system_account_info,
+        use_authority_bump_seed,
+        use_authority_signer_seeds,
+    )?;
+
+    use_authority.allowed_uses = args.number_of_uses;
+
+    UseAuthority::pack(use_authority, *use_authority_record_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn get_or_create_use_authority<'a>(
+    program_id: &Pubkey,
+    use_authority_info: &AccountInfo<'a>,
+    membership_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    rent_sysvar_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    bump: u8,
+    signers_seeds: &[&[u8]],
+) -> Result<UseAuthority, ProgramError> {
+    let unpack = UseAuthority::unpack(&use_authority_info.data.borrow_mut());
+
+    let proving_process = match unpack {
+        Ok(data) => Ok(data),
+        Err(_) => {
+            // create use authority account
+            create_or_allocate_account_raw(
+                *program_id,
+                use_authority_info,
+                rent_sysvar_info,
+                system_program_info,
+                payer_info,
+                UseAuthority::LEN,
+                signers_seeds,
+            )?;
+
+            msg!("New use authority account was created");
+
+            let mut data = UseAuthority::unpack_unchecked(&use_authority_info.data.borrow_mut())?;
+
+            data.init(*membership_info.key, bump);
+            Ok(data)
+        }
+    };
+
+    proving_process
+}
@@ -2,10 +2,15 @@
 
 use crate::{
     error::NFTPassError,
-    find_membership_program_address, find_pass_store_program_address,
+    find_market_config_program_address, find_membership_program_address,
+    find_pass_book_escrow, find_pass_program_address, find_pass_store_program_address, find_program_authority,
     find_trade_history_program_address, id,
     instruction::BuyPassArgs,
-    state::{Membership, MembershipState, PassBook, Payout, Store, TradeHistory, Uses, PREFIX},
+    math::SafeMath,
+    state::{
+        InitPassParams, MarketConfig, Membership, MembershipState, Pass, PassBook, PassState,
+        Payout, Purchase, PurchasePayout, Store, TradeHistory, Uses, ESCROW, PREFIX,
+    },
     utils::*,
 };
 
@@ -16,11 +21,13 @@ use solana_program::{
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
+    rent::Rent,
     sysvar::{clock::Clock, Sysvar},
 };
 
 use std::slice::Iter;
 
+use spl_associated_token_account::get_associated_token_address;
 use spl_token::state::Account;
 
 /// Process InitPass instruction
@@ -28,6 +35,25 @@ pub fn buy<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
     args: BuyPassArgs,
+) -> ProgramResult {
+    purchase(program_id, accounts, args, false)
+}
+
+/// Process BuyGiftPass instruction
+pub fn buy_gift<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: BuyPassArgs,
+) -> ProgramResult {
+    purchase(program_id, accounts, args, true)
+}
+
+/// Buy a pass paid by the user wallet, for the user itself or for the beneficiary of a gift
+fn purchase<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: BuyPassArgs,
+    is_gift: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let pass_book_info = next_account_info(account_info_iter)?;
@@ -37,6 +63,12 @@ pub fn buy<'a>(
     let payer_account_info = next_account_info(account_info_iter)?;
     let trade_history_info = next_account_info(account_info_iter)?;
     let membership_info = next_account_info(account_info_iter)?;
+    // the membership of a gift goes to the beneficiary, the trade history stays with the payer
+    let beneficiary_info = if is_gift {
+        next_account_info(account_info_iter)?
+    } else {
+        user_wallet_info
+    };
 
     let clock_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
@@ -48,29 +80,15 @@ pub fn buy<'a>(
     assert_signer(user_wallet_info)?;
 
     let mut passbook = PassBook::unpack(&pass_book_info.data.borrow_mut())?;
+    assert_purchase_terms(&passbook, args.expected_price, &args.expected_mint)?;
+    passbook.assert_on_sale(clock.unix_timestamp as u64)?;
 
     let mut pass_store = Store::unpack(&store_info.data.borrow_mut())?;
 
     let (store_key, _) = find_pass_store_program_address(program_id, &passbook.authority);
     assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
 
-    let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
-
-    if is_native {
-        assert_account_key(
-            user_wallet_info,
-            &user_token_account_info.key,
-            Some(NFTPassError::UserWalletMustMatchUserTokenAccount),
-        )?;
-    } else {
-        let user_token_account: Account = assert_initialized(user_token_account_info)?;
-        if user_token_account.mint != passbook.mint {
-            return Err(NFTPassError::PriceTokenMismatch.into());
-        }
-        if user_token_account.owner != *user_wallet_info.key {
-            return Err(ProgramError::IllegalOwner);
-        }
-    }
+    assert_user_token_account(&passbook, user_wallet_info, user_token_account_info)?;
 
     let (trade_history_key, trade_history_bump_seed) =
         find_trade_history_program_address(program_id, pass_book_info.key, user_wallet_info.key);
@@ -100,8 +118,15 @@ pub fn buy<'a>(
         trade_history_signer_seeds,
     )?;
 
+    passbook.assert_wallet_can_buy(trade_history.already_bought)?;
+    passbook.assert_allowlisted(
+        user_wallet_info.key,
+        args.allowlist_proof.as_ref(),
+        trade_history.already_bought,
+    )?;
+
     let (membership_key, membership_bump_seed) =
-        find_membership_program_address(program_id, &store_key, user_wallet_info.key);
+        find_membership_program_address(program_id, &store_key, beneficiary_info.key);
     assert_account_key(
         membership_info,
         &membership_key,
@@ -112,7 +137,7 @@ pub fn buy<'a>(
         PREFIX.as_bytes(),
         program_id.as_ref(),
         &store_key.to_bytes(),
-        &user_wallet_info.key.to_bytes(),
+        &beneficiary_info.key.to_bytes(),
         Membership::PREFIX.as_bytes(),
         &[membership_bump_seed],
     ];
@@ -131,7 +156,7 @@ pub fn buy<'a>(
     let (mut membership, is_new_membership) = get_or_create_membership(
         program_id,
         membership_info,
-        user_wallet_info,
+        beneficiary_info,
         store_info,
         payer_account_info,
         rent_info,
@@ -139,7 +164,9 @@ pub fn buy<'a>(
         membership_signer_seeds,
     )?;
 
-    let membership_expired = if let Some(expires_at) = membership.expires_at {
+    let membership_expired = if let Some(expires_at) =
+        membership.effective_expires_at(clock.unix_timestamp as u64)
+    {
         clock.unix_timestamp as u64 > expires_at
     } else {
         false
@@ -150,30 +177,79 @@ pub fn buy<'a>(
         return Err(NFTPassError::UserHasActiveMembership.into());
     }
 
+    // a lapsed membership may move over from another pass book
+    let previous_passbook = membership.passbook;
+    if previous_passbook != Some(*pass_book_info.key) {
+        passbook.increment_member_count()?;
+    }
     membership.expires_at = expires_at;
     membership.passbook = Some(*pass_book_info.key);
+    passbook.record_member_expiry(expires_at);
     if  membership.state != MembershipState::Activated {
         membership.state = MembershipState::Activated;
         pass_store.increment_active_membership_count()?;
     }
     if let Some(max_uses) = passbook.max_uses {
         membership.uses = Some(Uses {
-            remaining: 0,
+            remaining: max_uses,
             total: max_uses,
         })
     }
-    distribute_payout(
-        args.market_fee_basis_point as u64,
-        args.referral_share as u64,
-        args.referral_kick_back_share as u64,
-        &passbook,
+    membership.subscription = None;
+    membership.paused_at = None;
+    membership.pause_ends_at = None;
+    membership.paused_duration = 0;
+    let payouts = collect_payment(
+        program_id,
+        &mut passbook,
+        pass_book_info,
         &pass_store,
-        user_wallet_info.clone(),
-        user_token_account_info.clone(),
+        user_wallet_info,
+        user_token_account_info,
+        payer_account_info,
+        rent_info,
         clock,
         account_info_iter,
+        &[],
     )?;
+    // the purchase is kept to refund the payer within the refund window
+    membership.purchase = passbook.refund_window.map(|_| Purchase {
+        buyer: *user_wallet_info.key,
+        price: passbook.price,
+        purchased_at: clock.unix_timestamp as u64,
+        mint: passbook.mint,
+        payouts,
+    });
     msg!("DONE DISTRIBUTING");
+    if let Some(master_edition_mint) = passbook.master_edition_mint {
+        let pass_key = print_pass(
+            program_id,
+            &passbook,
+            pass_book_info,
+            &master_edition_mint,
+            pass_store.collection_mint.as_ref(),
+            user_wallet_info,
+            beneficiary_info,
+            payer_account_info,
+            rent_info,
+            system_account_info,
+            membership.expires_at,
+            account_info_iter,
+        )?;
+        membership.pass = Some(pass_key);
+    } else {
+        // a pass printed for an earlier purchase no longer carries this membership
+        membership.pass = None;
+    }
+    if let Some(previous_passbook) = previous_passbook {
+        if previous_passbook != *pass_book_info.key {
+            release_pass_book_member(
+                program_id,
+                &previous_passbook,
+                next_account_info(account_info_iter)?,
+            )?;
+        }
+    }
     if is_new_membership {
         pass_store.increment_membership_count()?;
     }
@@ -187,18 +263,261 @@ pub fn buy<'a>(
     Ok(())
 }
 
+/// Print an edition of the vaulted master edition to the pass owner and create its pass account,
+/// the user wallet is the mint authority of the new mint
+#[allow(clippy::too_many_arguments)]
+pub fn print_pass<'a>(
+    program_id: &Pubkey,
+    passbook: &PassBook,
+    pass_book_info: &AccountInfo<'a>,
+    master_edition_mint: &Pubkey,
+    collection_mint: Option<&Pubkey>,
+    user_wallet_info: &AccountInfo<'a>,
+    owner_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    expires_at: Option<u64>,
+    remaining_accounts: &mut Iter<'a, AccountInfo<'a>>,
+) -> Result<Pubkey, ProgramError> {
+    let token_program_info = next_account_info(remaining_accounts)?;
+    let program_authority_info = next_account_info(remaining_accounts)?;
+    let vault_info = next_account_info(remaining_accounts)?;
+    let master_edition_info = next_account_info(remaining_accounts)?;
+    let master_metadata_info = next_account_info(remaining_accounts)?;
+    let edition_marker_info = next_account_info(remaining_accounts)?;
+    let new_mint_info = next_account_info(remaining_accounts)?;
+    let new_metadata_info = next_account_info(remaining_accounts)?;
+    let new_edition_info = next_account_info(remaining_accounts)?;
+    let pass_info = next_account_info(remaining_accounts)?;
+    let token_metadata_program_info = next_account_info(remaining_accounts)?;
+    let pass_token_info = next_account_info(remaining_accounts)?;
+
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    assert_account_key(token_metadata_program_info, &mpl_token_metadata::id(), None)?;
+
+    let (program_authority_key, program_authority_bump_seed) = find_program_authority(program_id);
+    assert_account_key(
+        program_authority_info,
+        &program_authority_key,
+        Some(NFTPassError::InvalidProgramAuthority),
+    )?;
+    assert_account_key(
+        vault_info,
+        &get_associated_token_address(&program_authority_key, master_edition_mint),
+        Some(NFTPassError::InvalidVaultToken),
+    )?;
+
+    let (pass_key, pass_bump_seed) = find_pass_program_address(program_id, new_mint_info.key);
+    assert_account_key(pass_info, &pass_key, Some(NFTPassError::InvalidPassKey))?;
+
+    assert_owned_by(pass_token_info, &spl_token::id())?;
+    let pass_token = Account::unpack(&pass_token_info.data.borrow())?;
+    if pass_token.mint != *new_mint_info.key
+        || pass_token.owner != *owner_info.key
+        || pass_token.amount != 1
+    {
+        return Err(NFTPassError::NotPassHolder.into());
+    }
+
+    let program_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &[program_authority_bump_seed],
+    ];
+
+    mpl_mint_new_edition_from_master_edition_via_token(
+        new_metadata_info,
+        new_edition_info,
+        new_mint_info,
+        user_wallet_info,
+        payer_info,
+        program_authority_info,
+        program_authority_info,
+        vault_info,
+        master_metadata_info,
+        master_edition_info,
+        master_edition_mint,
+        edition_marker_info,
+        token_program_info,
+        system_program_info,
+        rent_info,
+        passbook.supply.error_increment()?,
+        program_authority_signer_seeds,
+    )?;
+
+    // group the passes of the store under its collection
+    if let Some(collection_mint) = collection_mint {
+        let collection_mint_info = next_account_info(remaining_accounts)?;
+        let collection_metadata_info = next_account_info(remaining_accounts)?;
+        let collection_edition_info = next_account_info(remaining_accounts)?;
+        assert_account_key(
+            collection_mint_info,
+            collection_mint,
+            Some(NFTPassError::InvalidCollectionMint),
+        )?;
+        mpl_set_and_verify_collection(
+            new_metadata_info,
+            program_authority_info,
+            payer_info,
+            program_authority_info,
+            collection_mint_info,
+            collection_metadata_info,
+            collection_edition_info,
+            program_authority_signer_seeds,
+        )?;
+    }
+
+    // soulbound passes are frozen in the buyer wallet by the program authority as delegate
+    if passbook.soulbound {
+        assert_signer(owner_info)?;
+        spl_token_approve(
+            pass_token_info.clone(),
+            program_authority_info.clone(),
+            owner_info.clone(),
+            1,
+        )?;
+        mpl_freeze_delegated_account(
+            program_authority_info,
+            pass_token_info,
+            new_edition_info,
+            new_mint_info,
+            token_program_info,
+            program_authority_signer_seeds,
+        )?;
+    }
+
+    let pass_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &new_mint_info.key.to_bytes(),
+        Pass::PREFIX.as_bytes(),
+        &[pass_bump_seed],
+    ];
+
+    create_or_allocate_account_raw(
+        *program_id,
+        pass_info,
+        rent_info,
+        system_program_info,
+        payer_info,
+        Pass::LEN,
+        pass_signer_seeds,
+    )?;
+
+    let mut pass = Pass::unpack_unchecked(&pass_info.data.borrow_mut())?;
+    pass.init(InitPassParams {
+        name: passbook.name.clone(),
+        description: passbook.description.clone(),
+        uri: passbook.uri.clone(),
+        owner: *owner_info.key,
+        pass_book: *pass_book_info.key,
+        mint: *new_mint_info.key,
+        expires_at,
+    });
+    pass.state = PassState::Activated;
+    pass.puff_out_data_fields();
+    Pass::pack(pass, *pass_info.data.borrow_mut())?;
+
+    Ok(pass_key)
+}
+
+/// Stop counting a membership moving to another pass book as a member of the previous one
+pub fn release_pass_book_member(
+    program_id: &Pubkey,
+    pass_book_key: &Pubkey,
+    pass_book_info: &AccountInfo,
+) -> ProgramResult {
+    assert_account_key(
+        pass_book_info,
+        pass_book_key,
+        Some(NFTPassError::InvalidPassBookKey),
+    )?;
+    // a deleted pass book has no members left to count
+    if pass_book_info.data_is_empty() || pass_book_info.owner != program_id {
+        return Ok(());
+    }
+
+    let mut pass_book = PassBook::unpack(&pass_book_info.data.borrow())?;
+    pass_book.decrement_member_count();
+    PassBook::pack(pass_book, *pass_book_info.data.borrow_mut())
+}
+
+/// Carry the membership expiry over to the pass printed for it, read from the next account if there is one
+pub fn update_pass_expiry(
+    program_id: &Pubkey,
+    membership: &Membership,
+    remaining_accounts: &mut Iter<AccountInfo>,
+) -> ProgramResult {
+    let pass_key = match membership.pass {
+        Some(pass_key) => pass_key,
+        None => return Ok(()),
+    };
+    let pass_info = next_account_info(remaining_accounts)?;
+    assert_account_key(pass_info, &pass_key, Some(NFTPassError::InvalidPassKey))?;
+    assert_owned_by(pass_info, program_id)?;
+
+    let mut pass = Pass::unpack(&pass_info.data.borrow())?;
+    pass.expires_at = membership.expires_at;
+    Pass::pack(pass, *pass_info.data.borrow_mut())
+}
+
+/// Check the pass book is still sold on the terms the buyer agreed to
+pub fn assert_purchase_terms(
+    passbook: &PassBook,
+    expected_price: u64,
+    expected_mint: &Pubkey,
+) -> Result<(), ProgramError> {
+    if passbook.price != expected_price {
+        return Err(NFTPassError::PriceMismatch.into());
+    }
+    if passbook.mint != *expected_mint {
+        return Err(NFTPassError::MintMismatch.into());
+    }
+    Ok(())
+}
+
+/// Check the user token account can pay for the pass book
+pub fn assert_user_token_account(
+    passbook: &PassBook,
+    user_wallet_info: &AccountInfo,
+    user_token_account_info: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
+
+    if is_native {
+        assert_account_key(
+            user_wallet_info,
+            &user_token_account_info.key,
+            Some(NFTPassError::UserWalletMustMatchUserTokenAccount),
+        )?;
+    } else {
+        let user_token_account: Account = assert_initialized(user_token_account_info)?;
+        if user_token_account.mint != passbook.mint {
+            return Err(NFTPassError::PriceTokenMismatch.into());
+        }
+        if user_token_account.owner != *user_wallet_info.key {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn transfer<'a>(
     is_native: bool,
     source_account_info: &AccountInfo<'a>,
     destination_account_info: &AccountInfo<'a>,
     owner_account_info: &AccountInfo<'a>,
     amount: u64,
+    signers_seeds: &[&[&[u8]]],
 ) -> Result<(), ProgramError> {
     if is_native {
-        native_transfer(
+        native_transfer_signed(
             source_account_info.clone(),
             destination_account_info.clone(),
             amount,
+            signers_seeds,
         )
     } else {
         // Transfer from source to token account
@@ -207,25 +526,24 @@ pub fn transfer<'a>(
             destination_account_info.clone(),
             owner_account_info.clone(),
             amount, // transfer master edition
-            &[],
+            signers_seeds,
         )
     }
 }
 
-pub fn pay_account<'a>(
-    amount: u64,
+/// Load the payout account of the authority and check its token account holds the mint
+pub fn load_payout_account(
     authority: &Pubkey,
-    passbook: &PassBook,
-    user_wallet: &AccountInfo<'a>,
-    user_token_account: &AccountInfo<'a>,
-    payout_account: &AccountInfo<'a>,
-    payout_token_account: &AccountInfo<'a>,
-) -> Result<(), ProgramError> {
-    let mut payout = Payout::unpack(&payout_account.data.borrow_mut())?;
-    if *authority != payout.authority && passbook.mint != payout.mint {
+    mint: &Pubkey,
+    payout_account: &AccountInfo,
+    payout_token_account: &AccountInfo,
+) -> Result<Payout, ProgramError> {
+    assert_owned_by(payout_account, &id())?;
+    let payout = Payout::unpack(&payout_account.data.borrow_mut())?;
+    if *authority != payout.authority || *mint != payout.mint {
         return Err(NFTPassError::InvalidPayoutKey.into());
     }
-    let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
+    let is_native = cmp_pubkeys(mint, &spl_token::native_mint::id());
     if is_native {
         if payout_token_account.key != payout_account.key {
             return Err(ProgramError::InvalidAccountData);
@@ -233,20 +551,40 @@ pub fn pay_account<'a>(
     } else {
         assert_owned_by(payout_token_account, &spl_token::id())?;
         let token_account: Account = assert_initialized(payout_token_account)?;
-        if token_account.mint != passbook.mint {
+        if token_account.mint != *mint {
             return Err(NFTPassError::PriceTokenMismatch.into());
         }
         if token_account.owner != *payout_account.key {
             return Err(ProgramError::IllegalOwner);
         }
     }
-    transfer(
-        is_native,
-        user_token_account,
-        payout_token_account,
-        user_wallet,
-        amount,
-    )?;
+    Ok(payout)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn pay_account<'a>(
+    amount: u64,
+    authority: &Pubkey,
+    passbook: &PassBook,
+    user_wallet: &AccountInfo<'a>,
+    user_token_account: &AccountInfo<'a>,
+    payout_account: &AccountInfo<'a>,
+    payout_token_account: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    let mut payout =
+        load_payout_account(authority, &passbook.mint, payout_account, payout_token_account)?;
+    let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
+    if amount > 0 {
+        transfer(
+            is_native,
+            user_token_account,
+            payout_token_account,
+            user_wallet,
+            amount,
+            signers_seeds,
+        )?;
+    }
     payout.cash_in = payout
         .cash_in
         .checked_add(amount)
@@ -255,73 +593,240 @@ pub fn pay_account<'a>(
     Ok(())
 }
 
-pub fn distribute_payout<'a>(
-    market_fee_basis_point: u64,
-    referral_share: u64,
-    referral_kick_back: u64,
-    passbook: &PassBook,
+/// Pay the pass book price into its escrow, or straight to the payout accounts without escrow.
+/// The signer seeds are given when the user wallet is a program delegate of the user token account.
+/// Returns the amounts paid into the payout accounts, none when the price went to the escrow
+#[allow(clippy::too_many_arguments)]
+pub fn collect_payment<'a>(
+    program_id: &Pubkey,
+    passbook: &mut PassBook,
+    pass_book_info: &AccountInfo<'a>,
     store: &Store,
-    user_wallet: AccountInfo<'a>,
-    user_token_account: AccountInfo<'a>,
+    user_wallet: &AccountInfo<'a>,
+    user_token_account: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
     clock: &Clock,
     remaining_accounts: &mut Iter<'a, AccountInfo<'a>>,
-) -> Result<(), ProgramError> {
-    if referral_share > 100 {
-        return Err(NFTPassError::WrongReferralShare.into());
-    }
-    if referral_kick_back > 100 {
-        return Err(NFTPassError::WrongReferralShare.into());
+    signers_seeds: &[&[&[u8]]],
+) -> Result<Vec<PurchasePayout>, ProgramError> {
+    if passbook.escrow_hold_period.is_none() {
+        return distribute_payout(
+            passbook,
+            store,
+            user_wallet.clone(),
+            user_token_account.clone(),
+            clock,
+            remaining_accounts,
+            signers_seeds,
+        );
     }
-    let amount_for_creators = calculate_shares_less_points(passbook.price, market_fee_basis_point)?;
-    let creator_payout_info = next_account_info(remaining_accounts)?;
-    let creator_payout_token_info = next_account_info(remaining_accounts)?;
-    let creator_payout = PayoutInfo {
-        authority: passbook.authority,
-        payout_account: creator_payout_info,
-        token_account: creator_payout_token_info,
-        share: 100,
-    };
 
-    distribute_payout_for_creators(
-        amount_for_creators,
+    let escrow_info = next_account_info(remaining_accounts)?;
+    let escrow_token_info = next_account_info(remaining_accounts)?;
+    assert_escrow_accounts(
+        program_id,
         passbook,
-        &user_wallet,
-        &user_token_account,
-        &creator_payout,
+        pass_book_info,
+        escrow_info,
+        escrow_token_info,
     )?;
 
-    let amount_for_market_place =
-        calculate_amount_for_points(passbook.price, market_fee_basis_point)?;
-    if let Some(market_authority) = passbook.market_authority {
-        let market_authority_account_info = next_account_info(remaining_accounts)?;
-        assert_signer(market_authority_account_info)?;
+    let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
+    if is_native && escrow_info.lamports() == 0 {
+        // the escrow stays rent exempt apart from the revenue it holds
+        let rent = &Rent::from_account_info(rent_info)?;
+        native_transfer(
+            payer_info.clone(),
+            escrow_info.clone(),
+            rent.minimum_balance(0),
+        )?;
+    }
+    transfer(
+        is_native,
+        user_token_account,
+        escrow_token_info,
+        user_wallet,
+        passbook.price,
+        signers_seeds,
+    )?;
+
+    let now = clock.unix_timestamp as u64;
+    passbook.deposit_escrow(passbook.price, store.is_referral_active(now), now)?;
+    Ok(Vec::new())
+}
+
+/// Check the escrow of the pass book and its token account, the escrow itself for native SOL
+pub fn assert_escrow_accounts(
+    program_id: &Pubkey,
+    passbook: &PassBook,
+    pass_book_info: &AccountInfo,
+    escrow_info: &AccountInfo,
+    escrow_token_info: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (escrow_key, escrow_bump_seed) = find_pass_book_escrow(program_id, pass_book_info.key);
+    assert_account_key(
+        escrow_info,
+        &escrow_key,
+        Some(NFTPassError::InvalidEscrowKey),
+    )?;
+
+    let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
+    if is_native {
         assert_account_key(
-            market_authority_account_info,
-            &market_authority,
-            Some(NFTPassError::InvalidMarketAuthority),
+            escrow_token_info,
+            &escrow_key,
+            Some(NFTPassError::InvalidEscrowKey),
         )?;
-        let market_payout_info = next_account_info(remaining_accounts)?;
-        let market_payout_token_info = next_account_info(remaining_accounts)?;
-        let market_amount = calculate_shares(amount_for_market_place, 100 - referral_share)?;
+    } else {
+        assert_owned_by(escrow_token_info, &spl_token::id())?;
+        let token_account: Account = assert_initialized(escrow_token_info)?;
+        if token_account.mint != passbook.mint {
+            return Err(NFTPassError::PriceTokenMismatch.into());
+        }
+        if token_account.owner != escrow_key {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+
+    Ok(escrow_bump_seed)
+}
+
+/// Move an amount out of the escrow of the pass book
+pub fn escrow_transfer<'a>(
+    program_id: &Pubkey,
+    passbook: &PassBook,
+    pass_book_info: &AccountInfo<'a>,
+    escrow_info: &AccountInfo<'a>,
+    escrow_token_info: &AccountInfo<'a>,
+    destination_info: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let escrow_bump_seed = assert_escrow_accounts(
+        program_id,
+        passbook,
+        pass_book_info,
+        escrow_info,
+        escrow_token_info,
+    )?;
+    let escrow_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &pass_book_info.key.to_bytes(),
+        ESCROW.as_bytes(),
+        &[escrow_bump_seed],
+    ];
+
+    if cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id()) {
+        native_transfer_signed(
+            escrow_info.clone(),
+            destination_info.clone(),
+            amount,
+            &[escrow_signer_seeds],
+        )
+    } else {
+        spl_token_transfer(
+            escrow_token_info.clone(),
+            destination_info.clone(),
+            escrow_info.clone(),
+            amount,
+            &[escrow_signer_seeds],
+        )
+    }
+}
+
+pub fn distribute_payout<'a>(
+    passbook: &PassBook,
+    store: &Store,
+    user_wallet: AccountInfo<'a>,
+    user_token_account: AccountInfo<'a>,
+    clock: &Clock,
+    remaining_accounts: &mut Iter<'a, AccountInfo<'a>>,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<Vec<PurchasePayout>, ProgramError> {
+    let payouts = calculate_payouts(
+        passbook,
+        store,
+        passbook.price,
+        store.is_referral_active(clock.unix_timestamp as u64),
+        remaining_accounts,
+    )?;
+    let mut purchase_payouts = Vec::with_capacity(payouts.len());
+    for (payout, amount) in payouts.iter() {
         pay_account(
-            market_amount,
-            &market_authority,
+            *amount,
+            &payout.authority,
             passbook,
             &user_wallet,
             &user_token_account,
-            market_payout_info,
-            market_payout_token_info,
+            payout.payout_account,
+            payout.token_account,
+            signers_seeds,
         )?;
-        msg!("DONE SEND PAYOUT FOR MARKET PLACE");
+        purchase_payouts.push(PurchasePayout {
+            authority: payout.authority,
+            amount: *amount,
+        });
+    }
+    msg!("DONE DISTRIBUTE PAYOUT");
+    Ok(purchase_payouts)
+}
+
+/// Split the price of a purchase between the payout accounts, the referrer is paid only if
+/// the referral was active. Creators come first in the order of the pass book, then the market
+/// place and the referrer
+pub fn calculate_payouts<'a>(
+    passbook: &PassBook,
+    store: &Store,
+    price: u64,
+    referral_active: bool,
+    remaining_accounts: &mut Iter<'a, AccountInfo<'a>>,
+) -> Result<Vec<(PayoutInfo<'a>, u64)>, ProgramError> {
+    let mut creator_payouts = Vec::with_capacity(passbook.creators.len());
+    for creator in passbook.creators.iter() {
+        let creator_payout_info = next_account_info(remaining_accounts)?;
+        let creator_payout_token_info = next_account_info(remaining_accounts)?;
+        creator_payouts.push(PayoutInfo {
+            authority: creator.address,
+            payout_account: creator_payout_info,
+            token_account: creator_payout_token_info,
+            share: creator.share,
+        });
+    }
+
+    // fees are set by the market place on chain, a pass book without market place pays none
+    let mut market_fee_basis_point = 0;
+    let mut referral_share = 0;
+    let mut referral_kick_back = 0;
+    let mut market_payout = None;
+    if let Some(market_authority) = passbook.market_authority {
+        let market_config_info = next_account_info(remaining_accounts)?;
+        let market_config = get_market_config(market_config_info, &market_authority)?;
+        market_fee_basis_point = market_config.market_fee_basis_point as u64;
+        referral_share = market_config.referral_share as u64;
+        referral_kick_back = market_config.referral_kick_back_share as u64;
+        let market_payout_info = next_account_info(remaining_accounts)?;
+        let market_payout_token_info = next_account_info(remaining_accounts)?;
+        market_payout = Some(PayoutInfo {
+            authority: market_authority,
+            payout_account: market_payout_info,
+            token_account: market_payout_token_info,
+            share: 100,
+        });
     }
-    msg!("DONE DISTRIBUTE PAYOUT FOR MARKET PLACE");
+
+    let amount_for_creators = calculate_shares_less_points(price, market_fee_basis_point)?;
+    let mut creator_amounts = split_between_creators(amount_for_creators, &creator_payouts)?;
+
+    let amount_for_market_place = calculate_amount_for_points(price, market_fee_basis_point)?;
+    let mut other_payouts = Vec::with_capacity(2);
+    if let Some(market_payout) = market_payout {
+        let market_amount = calculate_shares(amount_for_market_place, 100 - referral_share)?;
+        other_payouts.push((market_payout, market_amount));
+    }
+
     if let Some(referrer) = store.referrer {
-        if let Some(referral_end_date) = store.referral_end_date {
-            if clock.unix_timestamp as u64 > referral_end_date {
-                return Ok(());
-            }
-        }
-        let amount_for_referrer = calculate_shares(amount_for_market_place, referral_share)?;
+        // referral accounts are always passed, accounts following them must stay in place
         let referrer_account_info = next_account_info(remaining_accounts)?;
         assert_account_key(
             referrer_account_info,
@@ -330,75 +835,82 @@ pub fn distribute_payout<'a>(
         )?;
         let referrer_payout_info = next_account_info(remaining_accounts)?;
         let referrer_payout_token_info = next_account_info(remaining_accounts)?;
-        let referrer_kick_back_amount = calculate_shares(amount_for_referrer, referral_kick_back)?;
-        let referrer_amount = calculate_shares(amount_for_referrer, 100 - referral_kick_back)?;
-        pay_account(
-            referrer_amount,
-            &referrer,
-            passbook,
-            &user_wallet,
-            &user_token_account,
-            referrer_payout_info,
-            referrer_payout_token_info,
-        )?;
-        distribute_referral_payout_for_creators(
-            referrer_kick_back_amount,
-            passbook,
-            &user_wallet,
-            &user_token_account,
-            &creator_payout,
-        )?;
+        if referral_active {
+            let amount_for_referrer = calculate_shares(amount_for_market_place, referral_share)?;
+            let referrer_kick_back_amount =
+                calculate_shares(amount_for_referrer, referral_kick_back)?;
+            let referrer_amount =
+                calculate_shares(amount_for_referrer, 100 - referral_kick_back)?;
+            other_payouts.push((
+                PayoutInfo {
+                    authority: referrer,
+                    payout_account: referrer_payout_info,
+                    token_account: referrer_payout_token_info,
+                    share: 100,
+                },
+                referrer_amount,
+            ));
+            let kick_back_amounts =
+                split_between_creators(referrer_kick_back_amount, &creator_payouts)?;
+            for (creator_amount, kick_back_amount) in
+                creator_amounts.iter_mut().zip(kick_back_amounts)
+            {
+                *creator_amount = creator_amount.error_add(kick_back_amount)?;
+            }
+        }
     }
-    msg!("DONE DISTRIBUTE REFERRAL FOR MARKET PLACE");
-    Ok(())
+
+    // creators without anything to receive are skipped
+    let mut payouts: Vec<(PayoutInfo<'a>, u64)> = creator_payouts
+        .into_iter()
+        .zip(creator_amounts)
+        .filter(|(_, amount)| *amount > 0)
+        .collect();
+    payouts.extend(other_payouts);
+    Ok(payouts)
 }
 
-pub fn distribute_payout_for_creators<'a>(
-    amount: u64,
-    passbook: &PassBook,
-    user_wallet: &AccountInfo<'a>,
-    user_token_account: &AccountInfo<'a>,
-    payout_account: &PayoutInfo<'a>,
-) -> Result<(), ProgramError> {
-    if amount == 0 {
-        return Ok(());
-    }
-    let creator_amount = calculate_shares(amount, payout_account.share as u64)?;
-    pay_account(
-        creator_amount,
-        &payout_account.authority,
-        passbook,
-        user_wallet,
-        user_token_account,
-        payout_account.payout_account,
-        payout_account.token_account,
+/// Load the fee configuration of the market place selling the pass book
+pub fn get_market_config(
+    market_config_info: &AccountInfo,
+    market_authority: &Pubkey,
+) -> Result<MarketConfig, ProgramError> {
+    let (market_config_key, _) = find_market_config_program_address(&id(), market_authority);
+    assert_account_key(
+        market_config_info,
+        &market_config_key,
+        Some(NFTPassError::InvalidMarketConfigKey),
     )?;
-    Ok(())
+    assert_owned_by(market_config_info, &id())?;
+    MarketConfig::unpack(&market_config_info.data.borrow())
 }
 
-pub fn distribute_referral_payout_for_creators<'a>(
+/// Split the amount between creators by share, remainder dust goes to the first creator
+pub fn split_between_creators(
     amount: u64,
-    passbook: &PassBook,
-    user_wallet: &AccountInfo<'a>,
-    user_token_account: &AccountInfo<'a>,
-    payout_account: &PayoutInfo<'a>,
-) -> Result<(), ProgramError> {
+    payout_accounts: &[PayoutInfo],
+) -> Result<Vec<u64>, ProgramError> {
+    let mut creator_amounts = Vec::with_capacity(payout_accounts.len());
     if amount == 0 {
-        return Ok(());
+        creator_amounts.resize(payout_accounts.len(), 0);
+        return Ok(creator_amounts);
     }
-    let creator_amount = calculate_shares(amount, payout_account.share as u64)?;
-    pay_account(
-        creator_amount,
-        &payout_account.authority,
-        passbook,
-        user_wallet,
-        user_token_account,
-        payout_account.payout_account,
-        payout_account.token_account,
-    )?;
-    Ok(())
+    for payout_account in payout_accounts.iter() {
+        creator_amounts.push(calculate_shares(amount, payout_account.share as u64)?);
+    }
+    let distributed = creator_amounts
+        .iter()
+        .try_fold(0u64, |total, creator_amount| total.checked_add(*creator_amount))
+        .ok_or(NFTPassError::MathOverflow)?;
+    if let Some(first_amount) = creator_amounts.first_mut() {
+        *first_amount = first_amount
+            .checked_add(amount.checked_sub(distributed).ok_or(NFTPassError::MathOverflow)?)
+            .ok_or(NFTPassError::MathOverflow)?;
+    }
+    Ok(creator_amounts)
 }
 
+
 pub fn get_or_create_trade_history<'a>(
     program_id: &Pubkey,
     history_info: &AccountInfo<'a>,
@@ -0,0 +1,204 @@
+//! ChargeSubscription instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_membership_program_address, find_pass_store_program_address,
+    find_trade_history_program_address,
+    math::SafeMath,
+    processor::buy_pass_book::{collect_payment, get_or_create_trade_history, update_pass_expiry},
+    state::{Membership, MembershipState, PassBook, Store, Subscription, TradeHistory, PREFIX},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+use spl_token::state::Account;
+
+/// Process ChargeSubscription instruction
+pub fn charge_subscription<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let user_wallet_info = next_account_info(account_info_iter)?;
+    let user_token_account_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let trade_history_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(pass_book_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_owned_by(membership_info, program_id)?;
+
+    let mut passbook = PassBook::unpack(&pass_book_info.data.borrow())?;
+    let mut store = Store::unpack(&store_info.data.borrow())?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, &passbook.authority);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let (membership_key, membership_bump_seed) =
+        find_membership_program_address(program_id, &store_key, user_wallet_info.key);
+    assert_account_key(
+        membership_info,
+        &membership_key,
+        Some(NFTPassError::InvalidMembershipKey),
+    )?;
+
+    let mut membership = Membership::unpack(&membership_info.data.borrow())?;
+    if membership.passbook != Some(*pass_book_info.key) {
+        return Err(NFTPassError::InvalidPassBookKey.into());
+    }
+
+    let now = clock.unix_timestamp as u64;
+    let subscription = membership.assert_subscription_due(now)?.clone();
+    assert_account_key(
+        user_token_account_info,
+        &subscription.token_account,
+        Some(NFTPassError::InvalidSubscriptionTokenAccount),
+    )?;
+
+    let (trade_history_key, trade_history_bump_seed) =
+        find_trade_history_program_address(program_id, pass_book_info.key, user_wallet_info.key);
+    assert_account_key(
+        trade_history_info,
+        &trade_history_key,
+        Some(NFTPassError::InvalidTradeHistoryKey),
+    )?;
+    // the trade history is only created once the charge is sure to be made
+    let already_bought = if trade_history_info.data_is_empty() {
+        0
+    } else {
+        TradeHistory::unpack(&trade_history_info.data.borrow())?.already_bought
+    };
+
+    // a charge that can't be made ends the membership instead of failing the crank
+    if !is_chargeable(
+        &passbook,
+        &subscription,
+        user_wallet_info,
+        user_token_account_info,
+        membership_info,
+        already_bought,
+        now,
+    ) {
+        msg!("Subscription can't be charged, membership lapsed");
+        membership.state = MembershipState::Expired;
+        membership.subscription = None;
+        store.decrement_active_membership_count()?;
+        Membership::pack(membership, *membership_info.data.borrow_mut())?;
+        Store::pack(store, *store_info.data.borrow_mut())?;
+        return Ok(());
+    }
+
+    let trade_history_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &pass_book_info.key.to_bytes(),
+        &user_wallet_info.key.to_bytes(),
+        TradeHistory::PREFIX.as_bytes(),
+        &[trade_history_bump_seed],
+    ];
+    let mut trade_history = get_or_create_trade_history(
+        program_id,
+        trade_history_info,
+        pass_book_info,
+        user_wallet_info,
+        payer_info,
+        rent_info,
+        system_program_info,
+        trade_history_signer_seeds,
+    )?;
+
+    membership.renew(passbook.access, passbook.max_uses, now)?;
+    membership.purchase = None;
+    membership.subscription = if subscription.periods_left > 1 {
+        Some(Subscription {
+            periods_left: subscription.periods_left.error_sub(1)?,
+            ..subscription
+        })
+    } else {
+        None
+    };
+    passbook.record_member_expiry(membership.expires_at);
+
+    let membership_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &store_key.to_bytes(),
+        &user_wallet_info.key.to_bytes(),
+        Membership::PREFIX.as_bytes(),
+        &[membership_bump_seed],
+    ];
+    collect_payment(
+        program_id,
+        &mut passbook,
+        pass_book_info,
+        &store,
+        membership_info,
+        user_token_account_info,
+        payer_info,
+        rent_info,
+        clock,
+        account_info_iter,
+        &[membership_signer_seeds],
+    )?;
+    // the pass account, if any, follows the token program
+    let token_program_info = next_account_info(account_info_iter)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    update_pass_expiry(program_id, &membership, account_info_iter)?;
+
+    // like a renewal, a charge does not take a membership from max_supply
+    store.increment_pass_count()?;
+    trade_history.increment_already_bought()?;
+    PassBook::pack(passbook, *pass_book_info.data.borrow_mut())?;
+    Store::pack(store, *store_info.data.borrow_mut())?;
+    TradeHistory::pack(trade_history, *trade_history_info.data.borrow_mut())?;
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Check the pass book still sells at the subscribed price and the member token account
+/// holds and delegates enough to pay it
+fn is_chargeable(
+    passbook: &PassBook,
+    subscription: &Subscription,
+    user_wallet_info: &AccountInfo,
+    user_token_account_info: &AccountInfo,
+    membership_info: &AccountInfo,
+    already_bought: u64,
+    now: u64,
+) -> bool {
+    if passbook.assert_activated().is_err()
+        || passbook.assert_on_sale(now).is_err()
+        || passbook.assert_wallet_can_buy(already_bought).is_err()
+        || passbook.price != subscription.price
+    {
+        return false;
+    }
+    if *user_token_account_info.owner != spl_token::id() {
+        return false;
+    }
+    let token_account: Account = match assert_initialized(user_token_account_info) {
+        Ok(token_account) => token_account,
+        Err(_) => return false,
+    };
+    token_account.mint == passbook.mint
+        && token_account.owner == *user_wallet_info.key
+        && !token_account.is_frozen()
+        && token_account.delegate == COption::Some(*membership_info.key)
+        && token_account.delegated_amount >= passbook.price
+        && token_account.amount >= passbook.price
+}
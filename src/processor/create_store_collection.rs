@@ -0,0 +1,165 @@
+//! CreateStoreCollection instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_pass_collection_mint, find_pass_store_program_address, find_program_authority,
+    instruction::CreateStoreCollectionArgs,
+    state::{Store, COLLECTION_MINT, PREFIX},
+    utils::*,
+};
+use mpl_token_metadata::pda::{find_master_edition_account, find_metadata_account};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Mint;
+
+/// Process CreateStoreCollection instruction
+pub fn create_store_collection(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateStoreCollectionArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_token_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_edition_info = next_account_info(account_info_iter)?;
+    let program_authority_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let associated_token_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(store_info, program_id)?;
+    assert_signer(authority_info)?;
+    assert_signer(payer_info)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    assert_account_key(
+        associated_token_program_info,
+        &spl_associated_token_account::id(),
+        None,
+    )?;
+    assert_account_key(token_metadata_program_info, &mpl_token_metadata::id(), None)?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, authority_info.key);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let mut store = Store::unpack(&store_info.data.borrow_mut())?;
+    if store.collection_mint.is_some() {
+        return Err(NFTPassError::StoreCollectionAlreadyCreated.into());
+    }
+
+    let (collection_mint_key, collection_mint_bump_seed) =
+        find_pass_collection_mint(program_id, store_info.key);
+    assert_account_key(
+        collection_mint_info,
+        &collection_mint_key,
+        Some(NFTPassError::InvalidCollectionMint),
+    )?;
+    assert_account_key(
+        collection_metadata_info,
+        &find_metadata_account(&collection_mint_key).0,
+        Some(NFTPassError::InvalidCollectionMint),
+    )?;
+    assert_account_key(
+        collection_edition_info,
+        &find_master_edition_account(&collection_mint_key).0,
+        Some(NFTPassError::InvalidCollectionMint),
+    )?;
+
+    let (program_authority_key, program_authority_bump_seed) = find_program_authority(program_id);
+    assert_account_key(
+        program_authority_info,
+        &program_authority_key,
+        Some(NFTPassError::InvalidProgramAuthority),
+    )?;
+    assert_account_key(
+        collection_token_info,
+        &get_associated_token_address(&program_authority_key, &collection_mint_key),
+        Some(NFTPassError::InvalidVaultToken),
+    )?;
+
+    let collection_mint_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &store_info.key.to_bytes(),
+        COLLECTION_MINT.as_bytes(),
+        &[collection_mint_bump_seed],
+    ];
+    let program_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &[program_authority_bump_seed],
+    ];
+
+    // mint the single collection token to the program authority
+    create_or_allocate_account_raw(
+        spl_token::id(),
+        collection_mint_info,
+        rent_info,
+        system_program_info,
+        payer_info,
+        Mint::LEN,
+        collection_mint_signer_seeds,
+    )?;
+    spl_initialize_mint(collection_mint_info, program_authority_info, rent_info, 0)?;
+    create_associated_token_account_raw(
+        payer_info,
+        program_authority_info,
+        collection_mint_info,
+        collection_token_info,
+        token_program_info,
+        rent_info,
+        system_program_info,
+    )?;
+    spl_mint(
+        collection_mint_info,
+        collection_token_info,
+        program_authority_info,
+        1,
+        program_authority_signer_seeds,
+    )?;
+
+    create_metadata(
+        args.name,
+        args.uri,
+        args.symbol,
+        0,
+        None,
+        true,
+        collection_mint_info,
+        collection_metadata_info,
+        payer_info,
+        program_authority_info,
+        program_authority_info,
+        rent_info,
+        system_program_info,
+        program_authority_signer_seeds,
+    )?;
+    create_master_edition(
+        collection_edition_info,
+        collection_mint_info,
+        collection_metadata_info,
+        payer_info,
+        program_authority_info,
+        program_authority_info,
+        Some(0),
+        token_program_info,
+        rent_info,
+        system_program_info,
+        program_authority_signer_seeds,
+    )?;
+
+    store.collection_mint = Some(collection_mint_key);
+    Store::pack(store, *store_info.data.borrow_mut())?;
+
+    Ok(())
+}
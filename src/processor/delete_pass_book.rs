@@ -2,24 +2,41 @@
 
 use crate::{
     error::NFTPassError,
-    state::{PassBook},
+    find_pass_store_program_address, find_program_authority,
+    instruction::DeletePassBookArgs,
+    math::SafeMath,
+    state::{Membership, MembershipState, PassBook, PassBookState, Store, PREFIX},
     utils::*,
 };
+use mpl_token_metadata::pda::find_metadata_account;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    program_memory::sol_memset,
     program_pack::Pack,
     pubkey::Pubkey,
+    system_program,
+    sysvar::{clock::Clock, Sysvar},
 };
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account;
 
 /// Process DeletePassBook instruction
-pub fn delete_pass_book(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+pub fn delete_pass_book(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: DeletePassBookArgs,
+) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let pass_book_account = next_account_info(account_info_iter)?;
     let authority_account = next_account_info(account_info_iter)?;
     let refunder_account = next_account_info(account_info_iter)?;
+    let store_account = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
 
     assert_owned_by(pass_book_account, program_id)?;
+    assert_owned_by(store_account, program_id)?;
 
     assert_signer(&authority_account)?;
 
@@ -31,8 +48,207 @@ pub fn delete_pass_book(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progra
         Some(NFTPassError::InvalidCreatorKey),
     )?;
 
+    let (store_key, _) = find_pass_store_program_address(program_id, authority_account.key);
+    assert_account_key(store_account, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+    let mut store = Store::unpack(&store_account.data.borrow_mut())?;
+
+    if pass_book.state == PassBookState::Activated {
+        return Err(NFTPassError::PassBookIsActivated.into());
+    }
+
+    // the split of the escrow needs the pass book
+    if pass_book.has_escrow_balance() {
+        return Err(NFTPassError::EscrowNotSettled.into());
+    }
+
+    let now = clock.unix_timestamp as u64;
+    if !args.force && pass_book.has_live_members(now) {
+        return Err(NFTPassError::PassBookHasLiveMembers.into());
+    }
+
+    if let Some(master_edition_mint) = pass_book.master_edition_mint {
+        let vault_info = next_account_info(account_info_iter)?;
+        let mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let new_owner_token_info = next_account_info(account_info_iter)?;
+        let program_authority_info = next_account_info(account_info_iter)?;
+        let metadata_info = next_account_info(account_info_iter)?;
+        let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+        assert_account_key(token_program_info, &spl_token::id(), None)?;
+        assert_account_key(token_metadata_program_info, &mpl_token_metadata::id(), None)?;
+        assert_account_key(
+            metadata_info,
+            &find_metadata_account(&master_edition_mint).0,
+            Some(NFTPassError::InvalidMetadataAccount),
+        )?;
+        assert_account_key(
+            mint_info,
+            &master_edition_mint,
+            Some(NFTPassError::InvalidMintKey),
+        )?;
+
+        let (program_authority_key, program_authority_bump_seed) =
+            find_program_authority(program_id);
+        assert_account_key(
+            program_authority_info,
+            &program_authority_key,
+            Some(NFTPassError::InvalidProgramAuthority),
+        )?;
+        assert_account_key(
+            vault_info,
+            &get_associated_token_address(&program_authority_key, &master_edition_mint),
+            Some(NFTPassError::InvalidVaultToken),
+        )?;
+
+        let program_authority_signer_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &[program_authority_bump_seed],
+        ];
+
+        // Return the master edition and close the vault
+        let vault = Account::unpack(&vault_info.data.borrow())?;
+        if vault.amount > 0 {
+            spl_token_transfer(
+                vault_info.clone(),
+                new_owner_token_info.clone(),
+                program_authority_info.clone(),
+                vault.amount,
+                &[program_authority_signer_seeds],
+            )?;
+        }
+        spl_token_close_account(
+            vault_info.clone(),
+            refunder_account.clone(),
+            program_authority_info.clone(),
+            &[program_authority_signer_seeds],
+        )?;
+
+        // give the pass book authority back control of the master metadata
+        mpl_set_update_authority(
+            metadata_info,
+            program_authority_info,
+            pass_book.authority,
+            program_authority_signer_seeds,
+        )?;
+    }
+
+    if args.force {
+        let source_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let is_native = cmp_pubkeys(&pass_book.mint, &spl_token::native_mint::id());
+
+        if is_native {
+            assert_account_key(token_program_info, &system_program::id(), None)?;
+            assert_account_key(
+                source_info,
+                authority_account.key,
+                Some(NFTPassError::UserWalletMustMatchUserTokenAccount),
+            )?;
+        } else {
+            assert_account_key(token_program_info, &spl_token::id(), None)?;
+        }
+
+        let mut released_count: u64 = 0;
+        while let (Ok(membership_info), Ok(member_token_info)) = (
+            next_account_info(account_info_iter),
+            next_account_info(account_info_iter),
+        ) {
+            refund_member(
+                &pass_book,
+                pass_book_account.key,
+                &mut store,
+                membership_info,
+                member_token_info,
+                source_info,
+                authority_account,
+                is_native,
+                now,
+                program_id,
+            )?;
+            released_count = released_count.error_increment()?;
+        }
+
+        // every membership referring to the pass book must be released, live ones refunded
+        if released_count < pass_book.member_count {
+            return Err(NFTPassError::PassBookHasLiveMembers.into());
+        }
+    }
+
+    store.decrement_pass_book_count()?;
+    Store::pack(store, *store_account.data.borrow_mut())?;
+
     // Transfer PackCard tokens
+    sol_memset(*pass_book_account.data.borrow_mut(), 0, PassBook::LEN);
     empty_account_balance(pass_book_account, refunder_account)?;
-    
+
+    Ok(())
+}
+
+/// Release a membership referring to the deleted pass book, an active one is expired and refunded for the time left on it
+#[allow(clippy::too_many_arguments)]
+fn refund_member<'a>(
+    pass_book: &PassBook,
+    pass_book_key: &Pubkey,
+    store: &mut Store,
+    membership_info: &AccountInfo<'a>,
+    member_token_info: &AccountInfo<'a>,
+    source_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    is_native: bool,
+    now: u64,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    assert_owned_by(membership_info, program_id)?;
+    let mut membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+
+    if membership.passbook != Some(*pass_book_key) {
+        return Err(NFTPassError::InvalidPassBookKey.into());
+    }
+    if membership.state == MembershipState::Activated {
+        if !membership.is_exhausted(now) {
+            // refund what the member paid, the pass book price or mint may have changed since
+            let (price, mint) = match &membership.purchase {
+                Some(purchase) => (purchase.price, purchase.mint),
+                None => (pass_book.price, pass_book.mint),
+            };
+            if cmp_pubkeys(&mint, &spl_token::native_mint::id()) != is_native {
+                return Err(NFTPassError::MintMismatch.into());
+            }
+            let amount = pass_book.refund_amount(price, membership.effective_expires_at(now), now)?;
+            if is_native {
+                assert_account_key(
+                    member_token_info,
+                    &membership.owner,
+                    Some(NFTPassError::UserWalletMustMatchUserTokenAccount),
+                )?;
+                if amount > 0 {
+                    native_transfer(source_info.clone(), member_token_info.clone(), amount)?;
+                }
+            } else {
+                let member_token = Account::unpack(&member_token_info.data.borrow())?;
+                if member_token.owner != membership.owner || member_token.mint != mint {
+                    return Err(NFTPassError::UserWalletMustMatchUserTokenAccount.into());
+                }
+                if amount > 0 {
+                    spl_token_transfer(
+                        source_info.clone(),
+                        member_token_info.clone(),
+                        authority_info.clone(),
+                        amount,
+                        &[],
+                    )?;
+                }
+            }
+        }
+        membership.state = MembershipState::Expired;
+        store.decrement_active_membership_count()?;
+    }
+
+    // released from the deleted pass book, the membership can't be counted twice
+    membership.passbook = None;
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+
     Ok(())
 }
@@ -2,10 +2,16 @@
 
 use crate::{
     error::NFTPassError,
+    find_program_authority,
     instruction::EditPassBookArgs,
-    state::{PassBook, MAX_DESCRIPTION_LEN, MAX_URI_LENGTH},
+    processor::init_pass_book::get_or_create_payout_account,
+    state::{PassBook, MAX_DESCRIPTION_LEN, MAX_NAME_LENGTH, MAX_URI_LENGTH, PREFIX},
     utils::*,
 };
+use mpl_token_metadata::{
+    pda::find_metadata_account,
+    state::{DataV2, Metadata},
+};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -23,14 +29,53 @@ pub fn edit_pass_book(
     let account_info_iter = &mut accounts.iter();
     let pass_book_account = next_account_info(account_info_iter)?;
     let authority_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let rent_account = next_account_info(account_info_iter)?;
+    let system_account = next_account_info(account_info_iter)?;
     
     assert_owned_by(pass_book_account, program_id)?;
     assert_signer(&authority_account)?;
 
-    let mint_account = next_account_info(account_info_iter).ok();
-
     let mut pass_book = PassBook::unpack(&pass_book_account.data.borrow_mut())?;
 
+    // master metadata accounts are passed only when the name or uri change
+    let metadata_changes = match pass_book.master_edition_mint {
+        Some(master_edition_mint) if args.name.is_some() || args.uri.is_some() => {
+            let metadata_account = next_account_info(account_info_iter)?;
+            let program_authority_account = next_account_info(account_info_iter)?;
+            let token_metadata_program_account = next_account_info(account_info_iter)?;
+            Some((
+                master_edition_mint,
+                metadata_account,
+                program_authority_account,
+                token_metadata_program_account,
+                args.name.clone(),
+                args.uri.clone(),
+            ))
+        }
+        _ => None,
+    };
+
+    // payout accounts of the new creators come before the optional mint,
+    // a new mint without new creators needs payout accounts of the current ones
+    let payout_creators_count = match &args.creators {
+        Some(creators) => Some(creators.len()),
+        None if !account_info_iter.as_slice().is_empty() => Some(pass_book.creators.len()),
+        None => None,
+    };
+    let creator_payout_accounts = match payout_creators_count {
+        Some(creators_count) => {
+            let mut payout_accounts = Vec::with_capacity(creators_count * 2);
+            for _ in 0..creators_count * 2 {
+                payout_accounts.push(next_account_info(account_info_iter)?.clone());
+            }
+            Some(payout_accounts)
+        }
+        None => None,
+    };
+
+    let mint_account = next_account_info(account_info_iter).ok();
+
     assert_account_key(authority_account, &pass_book.authority, 
         Some(NFTPassError::InvalidCreatorKey),)?;
 
@@ -38,6 +83,42 @@ pub fn edit_pass_book(
 
     apply_changes(&mut pass_book, args, mint_account)?;
 
+    if let Some((
+        master_edition_mint,
+        metadata_account,
+        program_authority_account,
+        token_metadata_program_account,
+        name,
+        uri,
+    )) = metadata_changes
+    {
+        sync_master_metadata(
+            program_id,
+            &master_edition_mint,
+            metadata_account,
+            program_authority_account,
+            token_metadata_program_account,
+            name,
+            uri,
+        )?;
+    }
+
+    if let Some(payout_accounts) = creator_payout_accounts {
+        assert_signer(payer_account)?;
+        let payout_accounts_iter = &mut payout_accounts.iter();
+        for creator in pass_book.creators.iter() {
+            get_or_create_payout_account(
+                program_id,
+                &creator.address,
+                payout_accounts_iter,
+                payer_account,
+                rent_account,
+                system_account,
+                &pass_book.mint,
+            )?;
+        }
+    }
+
     pass_book.puff_out_data_fields();
 
     PassBook::pack(pass_book, *pass_book_account.data.borrow_mut())?;
@@ -45,11 +126,78 @@ pub fn edit_pass_book(
     Ok(())
 }
 
+/// Update name and uri of the master metadata unless it is immutable
+fn sync_master_metadata<'a>(
+    program_id: &Pubkey,
+    master_edition_mint: &Pubkey,
+    metadata_account: &AccountInfo<'a>,
+    program_authority_account: &AccountInfo<'a>,
+    token_metadata_program_account: &AccountInfo<'a>,
+    name: Option<String>,
+    uri: Option<String>,
+) -> ProgramResult {
+    assert_account_key(
+        token_metadata_program_account,
+        &mpl_token_metadata::id(),
+        None,
+    )?;
+    assert_account_key(
+        metadata_account,
+        &find_metadata_account(master_edition_mint).0,
+        Some(NFTPassError::InvalidMetadataAccount),
+    )?;
+
+    let (program_authority_key, program_authority_bump_seed) = find_program_authority(program_id);
+    assert_account_key(
+        program_authority_account,
+        &program_authority_key,
+        Some(NFTPassError::InvalidProgramAuthority),
+    )?;
+
+    let metadata = Metadata::from_account_info(metadata_account)?;
+
+    // an immutable master metadata is left as is
+    if !metadata.is_mutable {
+        return Ok(());
+    }
+
+    let data = DataV2 {
+        name: name.unwrap_or_else(|| trim_padding(&metadata.data.name)),
+        symbol: trim_padding(&metadata.data.symbol),
+        uri: uri.unwrap_or_else(|| trim_padding(&metadata.data.uri)),
+        seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        creators: metadata.data.creators,
+        collection: metadata.collection,
+        uses: metadata.uses,
+    };
+
+    let program_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &[program_authority_bump_seed],
+    ];
+
+    mpl_update_metadata_accounts_v2(
+        metadata_account,
+        program_authority_account,
+        data,
+        program_authority_signer_seeds,
+    )
+}
+
+/// Strip the null bytes the metadata program pads strings with
+fn trim_padding(value: &str) -> String {
+    value.trim_matches(char::from(0)).to_string()
+}
+
 fn apply_changes(pass_book: &mut PassBook, changes: EditPassBookArgs, mint_account: Option<&AccountInfo>) -> Result<(), ProgramError> {
     if let Some(new_name) = changes.name {
         if new_name == pass_book.name {
             return Err(NFTPassError::CantSetTheSameValue.into());
         }
+        if new_name.len() > MAX_NAME_LENGTH {
+            return Err(NFTPassError::NameTooLong.into());
+        }
         pass_book.name = new_name;
     }
 
@@ -87,10 +235,54 @@ fn apply_changes(pass_book: &mut PassBook, changes: EditPassBookArgs, mint_accou
         pass_book.price = new_price;
     }
 
+    if let Some(creators) = changes.creators {
+        if creators == pass_book.creators {
+            return Err(NFTPassError::CantSetTheSameValue.into());
+        }
+        PassBook::assert_valid_creators(&creators)?;
+        pass_book.creators = creators;
+    }
+
+    if let Some(max_per_wallet) = changes.max_per_wallet {
+        if max_per_wallet == pass_book.max_per_wallet {
+            return Err(NFTPassError::CantSetTheSameValue.into());
+        }
+        if max_per_wallet == Some(0) {
+            return Err(NFTPassError::WrongMaxPerWallet.into());
+        }
+        pass_book.max_per_wallet = max_per_wallet;
+    }
+
+    if let Some(sale_start) = changes.sale_start {
+        if sale_start == pass_book.sale_start {
+            return Err(NFTPassError::CantSetTheSameValue.into());
+        }
+        pass_book.sale_start = sale_start;
+    }
+
+    if let Some(sale_end) = changes.sale_end {
+        if sale_end == pass_book.sale_end {
+            return Err(NFTPassError::CantSetTheSameValue.into());
+        }
+        pass_book.sale_end = sale_end;
+    }
+
+    PassBook::assert_valid_sale_window(pass_book.sale_start, pass_book.sale_end)?;
+
+    if let Some(allowlist_root) = changes.allowlist_root {
+        if allowlist_root == pass_book.allowlist_root {
+            return Err(NFTPassError::CantSetTheSameValue.into());
+        }
+        pass_book.allowlist_root = allowlist_root;
+    }
+
     if let Some(new_mint_account) = mint_account {
         if *new_mint_account.key == pass_book.mint {
             return Err(NFTPassError::CantSetTheSameValue.into());
         }
+        if pass_book.has_escrow_balance() {
+            return Err(NFTPassError::EscrowNotSettled.into());
+        }
         let is_native = cmp_pubkeys(new_mint_account.key, &spl_token::native_mint::id());
 
         if !is_native {
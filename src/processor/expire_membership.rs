@@ -0,0 +1,73 @@
+//! ExpireMembership instruction processing
+
+use crate::{
+    error::NFTPassError,
+    state::{Membership, MembershipState, Store},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Process ExpireMembership instruction
+pub fn expire_membership(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let membership_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let caller_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+    let rent = &Rent::from_account_info(rent_info)?;
+
+    assert_owned_by(membership_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+    let mut store = Store::unpack(&store_info.data.borrow_mut())?;
+
+    assert_account_key(
+        store_info,
+        &membership.store,
+        Some(NFTPassError::InvalidStoreKey),
+    )?;
+
+    if membership.state != MembershipState::Activated {
+        return Err(NFTPassError::MembershipNotActivated.into());
+    }
+
+    if !membership.is_exhausted(clock.unix_timestamp as u64) {
+        return Err(NFTPassError::MembershipStillActive.into());
+    }
+
+    // a subscribed membership is renewed or ended by ChargeSubscription
+    if membership.subscription.is_some() {
+        return Err(NFTPassError::MembershipHasSubscription.into());
+    }
+
+    membership.state = MembershipState::Expired;
+    store.decrement_active_membership_count()?;
+
+    // pay the bounty only from lamports above the store rent exemption
+    let bounty = store.expiry_bounty;
+    let available = store_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(store_info.data_len()));
+    if bounty > 0 && available >= bounty {
+        **store_info.try_borrow_mut_lamports()? -= bounty;
+        **caller_info.try_borrow_mut_lamports()? += bounty;
+    } else if bounty > 0 {
+        msg!("Store balance is too low to pay the expiry bounty");
+    }
+
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+    Store::pack(store, *store_info.data.borrow_mut())?;
+
+    Ok(())
+}
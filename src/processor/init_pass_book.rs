@@ -2,12 +2,18 @@
 
 use crate::{
     error::NFTPassError,
-    find_pass_store_program_address, find_payout_program_address,
+    find_pass_book_program_address, find_pass_store_program_address,
+    find_payout_program_address, find_program_authority,
     instruction::InitPassBookArgs,
-    state::{InitPassBook, PassBook, Payout, Store, MAX_NAME_LENGTH, MAX_URI_LENGTH, PREFIX},
+    state::{Creator, InitPassBook, PassBook, Payout, Store, MAX_NAME_LENGTH, MAX_URI_LENGTH, PREFIX},
     utils::*,
 };
 
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account},
+    state::{MasterEditionV2, Metadata},
+};
+
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -41,7 +47,38 @@ pub fn init_pass_book(
     let system_account_info = next_account_info(account_info_iter)?;
     let clock = &Clock::from_account_info(clock_info)?;
 
-    assert_signer(pass_book_info)?;
+    let master_edition_accounts = if args.has_master_edition {
+        Some(MasterEditionAccounts {
+            source_token_account: next_account_info(account_info_iter)?,
+            vault: next_account_info(account_info_iter)?,
+            program_authority: next_account_info(account_info_iter)?,
+            mint: next_account_info(account_info_iter)?,
+            metadata: next_account_info(account_info_iter)?,
+            edition: next_account_info(account_info_iter)?,
+            token_program: next_account_info(account_info_iter)?,
+            associated_token_program: next_account_info(account_info_iter)?,
+        })
+    } else {
+        None
+    };
+
+    // a pass book vaulting a master edition lives at the address derived from its mint
+    let pass_book_bump_seed = match &master_edition_accounts {
+        Some(master_edition) => {
+            let (pass_book_key, pass_book_bump_seed) =
+                find_pass_book_program_address(program_id, master_edition.mint.key);
+            assert_account_key(
+                pass_book_info,
+                &pass_book_key,
+                Some(NFTPassError::InvalidPassBookKey),
+            )?;
+            Some(pass_book_bump_seed)
+        }
+        None => {
+            assert_signer(pass_book_info)?;
+            None
+        }
+    };
     assert_signer(creator_info)?;
 
     let is_native = cmp_pubkeys(mint_info.key, &spl_token::native_mint::id());
@@ -78,13 +115,32 @@ pub fn init_pass_book(
         Some(NFTPassError::InvalidCreatorKey),
     )?;
 
-    let mut pass_book = get_or_create_passbook(
-        program_id,
-        pass_book_info,
-        payer_account_info,
-        rent_info,
-        system_account_info,
-    )?;
+    let mut pass_book = match (&master_edition_accounts, pass_book_bump_seed) {
+        (Some(master_edition), Some(pass_book_bump_seed)) => {
+            let pass_book_signer_seeds = &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                &master_edition.mint.key.to_bytes(),
+                &[pass_book_bump_seed],
+            ];
+            get_or_create_passbook(
+                program_id,
+                pass_book_info,
+                payer_account_info,
+                rent_info,
+                system_account_info,
+                Some(pass_book_signer_seeds),
+            )?
+        }
+        _ => get_or_create_passbook(
+            program_id,
+            pass_book_info,
+            payer_account_info,
+            rent_info,
+            system_account_info,
+            None,
+        )?,
+    };
 
     // PassBook::unpack_unchecked(&pass_book_info.data.borrow_mut())?;
 
@@ -118,15 +174,56 @@ pub fn init_pass_book(
         }
     }
 
-    get_or_create_payout_account(
-        program_id,
-        &creator_info.key,
-        account_info_iter,
-        payer_account_info,
-        rent_info,
-        system_account_info,
-        mint_info,
-    )?;
+    if args.max_per_wallet == Some(0) {
+        return Err(NFTPassError::WrongMaxPerWallet.into());
+    }
+
+    PassBook::assert_valid_sale_window(args.sale_start, args.sale_end)?;
+
+    if args.soulbound && master_edition_accounts.is_none() {
+        return Err(NFTPassError::SoulboundWithoutMasterEdition.into());
+    }
+
+    if args.refund_window.is_some() && master_edition_accounts.is_some() {
+        return Err(NFTPassError::RefundWindowWithMasterEdition.into());
+    }
+    PassBook::assert_valid_refund_window(args.refund_window, args.escrow_hold_period)?;
+    // only the time left on a membership can be paused
+    if args.max_pause_days.is_some() && args.access.is_none() {
+        return Err(NFTPassError::PauseRequiresAccess.into());
+    }
+
+    if let Some(master_edition) = &master_edition_accounts {
+        vault_master_edition(
+            program_id,
+            master_edition,
+            creator_info,
+            payer_account_info,
+            rent_info,
+            system_account_info,
+            args.max_supply,
+        )?;
+    }
+
+    let creators = args.creators.unwrap_or_else(|| {
+        vec![Creator {
+            address: *creator_info.key,
+            share: 100,
+        }]
+    });
+    PassBook::assert_valid_creators(&creators)?;
+
+    for creator in creators.iter() {
+        get_or_create_payout_account(
+            program_id,
+            &creator.address,
+            account_info_iter,
+            payer_account_info,
+            rent_info,
+            system_account_info,
+            mint_info.key,
+        )?;
+    }
 
     let market_authority = if args.has_market_authority {
         let market_authority_account = next_account_info(account_info_iter)?;
@@ -138,7 +235,7 @@ pub fn init_pass_book(
             payer_account_info,
             rent_info,
             system_account_info,
-            mint_info,
+            mint_info.key,
         )?;
         Some(*market_authority_account.key)
     } else {
@@ -154,7 +251,7 @@ pub fn init_pass_book(
             payer_account_info,
             rent_info,
             system_account_info,
-            mint_info,
+            mint_info.key,
         )?;
         store.referrer = Some(*referrer_account.key);
         store.referral_end_date = args.referral_end_date;
@@ -173,6 +270,18 @@ pub fn init_pass_book(
         price: args.price,
         mint: *mint_info.key,
         market_authority: market_authority,
+        creators,
+        max_per_wallet: args.max_per_wallet,
+        sale_start: args.sale_start,
+        sale_end: args.sale_end,
+        allowlist_root: args.allowlist_root,
+        master_edition_mint: master_edition_accounts
+            .as_ref()
+            .map(|master_edition| *master_edition.mint.key),
+        soulbound: args.soulbound,
+        refund_window: args.refund_window,
+        escrow_hold_period: args.escrow_hold_period,
+        max_pause_days: args.max_pause_days,
     });
 
     pass_book.puff_out_data_fields();
@@ -183,6 +292,99 @@ pub fn init_pass_book(
     Ok(())
 }
 
+/// Accounts of the master edition vaulted by a pass book
+pub struct MasterEditionAccounts<'a, 'b> {
+    pub source_token_account: &'a AccountInfo<'b>,
+    pub vault: &'a AccountInfo<'b>,
+    pub program_authority: &'a AccountInfo<'b>,
+    pub mint: &'a AccountInfo<'b>,
+    pub metadata: &'a AccountInfo<'b>,
+    pub edition: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub associated_token_program: &'a AccountInfo<'b>,
+}
+
+/// Check the master edition can print the pass book supply and move its token to the program vault
+pub fn vault_master_edition<'a>(
+    program_id: &Pubkey,
+    master_edition: &MasterEditionAccounts<'_, 'a>,
+    authority_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    rent_sysvar_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    max_supply: Option<u64>,
+) -> Result<(), ProgramError> {
+    assert_account_key(master_edition.token_program, &spl_token::id(), None)?;
+    assert_account_key(
+        master_edition.associated_token_program,
+        &spl_associated_token_account::id(),
+        None,
+    )?;
+    assert_owned_by(master_edition.mint, &spl_token::id())?;
+
+    assert_account_key(
+        master_edition.metadata,
+        &find_metadata_account(master_edition.mint.key).0,
+        Some(NFTPassError::InvalidMintKey),
+    )?;
+    assert_owned_by(master_edition.metadata, &mpl_token_metadata::id())?;
+    assert_account_key(
+        master_edition.edition,
+        &find_master_edition_account(master_edition.mint.key).0,
+        Some(NFTPassError::InvalidMintKey),
+    )?;
+    assert_owned_by(master_edition.edition, &mpl_token_metadata::id())?;
+
+    // every pass of the book must be printable from the master edition
+    let master_edition_data = MasterEditionV2::from_account_info(master_edition.edition)?;
+    if let Some(master_max_supply) = master_edition_data.max_supply {
+        let available = master_max_supply.saturating_sub(master_edition_data.supply);
+        match max_supply {
+            Some(max_supply) if max_supply <= available => {}
+            _ => return Err(NFTPassError::WrongMasterSupply.into()),
+        }
+    }
+
+    let (program_authority_key, _) = find_program_authority(program_id);
+    assert_account_key(
+        master_edition.program_authority,
+        &program_authority_key,
+        Some(NFTPassError::InvalidProgramAuthority),
+    )?;
+
+    // printed editions inherit the master update authority, the program signs for them
+    let metadata = Metadata::from_account_info(master_edition.metadata)?;
+    if metadata.update_authority != program_authority_key {
+        return Err(NFTPassError::InvalidUpdateAuthorityKey.into());
+    }
+
+    assert_account_key(
+        master_edition.vault,
+        &get_associated_token_address(&program_authority_key, master_edition.mint.key),
+        Some(NFTPassError::InvalidVaultToken),
+    )?;
+
+    if master_edition.vault.data_is_empty() {
+        create_associated_token_account_raw(
+            payer_info,
+            master_edition.program_authority,
+            master_edition.mint,
+            master_edition.vault,
+            master_edition.token_program,
+            rent_sysvar_info,
+            system_program_info,
+        )?;
+    }
+
+    spl_token_transfer(
+        master_edition.source_token_account.clone(),
+        master_edition.vault.clone(),
+        authority_info.clone(),
+        1,
+        &[],
+    )
+}
+
 pub fn get_pass_store_data<'a>(
     program_id: &Pubkey,
     store_info: &AccountInfo<'a>,
@@ -228,6 +430,7 @@ pub fn get_or_create_passbook<'a>(
     payer_info: &AccountInfo<'a>,
     rent_sysvar_info: &AccountInfo<'a>,
     system_program_info: &AccountInfo<'a>,
+    signers_seeds: Option<&[&[u8]]>,
 ) -> Result<PassBook, ProgramError> {
     // set up pass store account
 
@@ -237,14 +440,25 @@ pub fn get_or_create_passbook<'a>(
         Ok(data) => Ok(data),
         Err(_) => {
             // create pass store account
-            create_or_new_account_raw(
-                *program_id,
-                passbook_info,
-                rent_sysvar_info,
-                system_program_info,
-                payer_info,
-                PassBook::LEN,
-            )?;
+            match signers_seeds {
+                Some(signers_seeds) => create_or_allocate_account_raw(
+                    *program_id,
+                    passbook_info,
+                    rent_sysvar_info,
+                    system_program_info,
+                    payer_info,
+                    PassBook::LEN,
+                    signers_seeds,
+                )?,
+                None => create_or_new_account_raw(
+                    *program_id,
+                    passbook_info,
+                    rent_sysvar_info,
+                    system_program_info,
+                    payer_info,
+                    PassBook::LEN,
+                )?,
+            }
 
             msg!("New passbook account was created");
             Ok(PassBook::unpack_unchecked(
@@ -263,13 +477,13 @@ pub fn get_or_create_payout_account<'a>(
     payer_info: &AccountInfo<'a>,
     rent_sysvar_info: &AccountInfo<'a>,
     system_program_info: &AccountInfo<'a>,
-    mint_info: &AccountInfo<'a>,
+    mint: &Pubkey,
 ) -> Result<(), ProgramError> {
     // set up pass store account
     let payout_info = next_account_info(remaining_accounts)?;
     let treasury_holder_info = next_account_info(remaining_accounts)?;
     let (payout_key, payout_bump_seed) =
-        find_payout_program_address(program_id, authority, mint_info.key);
+        find_payout_program_address(program_id, authority, mint);
     assert_account_key(
         payout_info,
         &payout_key,
@@ -279,7 +493,7 @@ pub fn get_or_create_payout_account<'a>(
         PREFIX.as_bytes(),
         program_id.as_ref(),
         &authority.to_bytes(),
-        &mint_info.key.to_bytes(),
+        &mint.to_bytes(),
         Payout::PREFIX.as_bytes(),
         &[payout_bump_seed],
     ];
@@ -288,14 +502,14 @@ pub fn get_or_create_payout_account<'a>(
     match unpack {
         Ok(_) => Ok(()),
         Err(_) => {
-            let is_native = cmp_pubkeys(mint_info.key, &spl_token::native_mint::id());
+            let is_native = cmp_pubkeys(mint, &spl_token::native_mint::id());
             if is_native {
                 if treasury_holder_info.key != payout_info.key {
                     return Err(ProgramError::InvalidAccountData);
                 }
             } else {
                 let associated_token_account =
-                    get_associated_token_address(&payout_key, &mint_info.key);
+                    get_associated_token_address(&payout_key, mint);
 
                 // Check, that provided destination is associated token account
                 if associated_token_account != *treasury_holder_info.key {
@@ -320,7 +534,7 @@ pub fn get_or_create_payout_account<'a>(
 
             let mut data = Payout::unpack_unchecked(&payout_info.data.borrow_mut())?;
 
-            data.init(*authority, *mint_info.key, *treasury_holder_info.key);
+            data.init(*authority, *mint, *treasury_holder_info.key);
             Payout::pack(data, *payout_info.data.borrow_mut())?;
             Ok(())
         }
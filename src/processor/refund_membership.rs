@@ -0,0 +1,214 @@
+//! RefundMembership instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_membership_program_address, find_pass_store_program_address,
+    find_payout_program_address, find_trade_history_program_address,
+    processor::buy_pass_book::{escrow_transfer, load_payout_account},
+    state::{
+        Membership, MembershipState, PassBook, Payout, PurchasePayout, Store, TradeHistory,
+        PREFIX,
+    },
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{clock::Clock, Sysvar},
+};
+use spl_token::state::Account;
+
+/// Process RefundMembership instruction
+pub fn refund_membership<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let member_wallet_info = next_account_info(account_info_iter)?;
+    let trade_history_info = next_account_info(account_info_iter)?;
+    let buyer_token_account_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+    let rent = &Rent::from_account_info(rent_info)?;
+
+    assert_owned_by(pass_book_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_owned_by(membership_info, program_id)?;
+    assert_owned_by(trade_history_info, program_id)?;
+    assert_signer(member_wallet_info)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    assert_account_key(system_program_info, &system_program::id(), None)?;
+
+    let mut passbook = PassBook::unpack(&pass_book_info.data.borrow_mut())?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, &passbook.authority);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+    let mut store = Store::unpack(&store_info.data.borrow_mut())?;
+
+    let (membership_key, _) =
+        find_membership_program_address(program_id, &store_key, member_wallet_info.key);
+    assert_account_key(
+        membership_info,
+        &membership_key,
+        Some(NFTPassError::InvalidMembershipKey),
+    )?;
+    let mut membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+    if membership.passbook != Some(*pass_book_info.key) {
+        return Err(NFTPassError::InvalidPassBookKey.into());
+    }
+
+    let purchase = membership
+        .assert_refundable(passbook.refund_window, clock.unix_timestamp as u64)?
+        .clone();
+
+    // the wallet that paid gets the refund, the payer of a gift and not its beneficiary
+    let (trade_history_key, _) =
+        find_trade_history_program_address(program_id, pass_book_info.key, &purchase.buyer);
+    assert_account_key(
+        trade_history_info,
+        &trade_history_key,
+        Some(NFTPassError::InvalidTradeHistoryKey),
+    )?;
+    let mut trade_history = TradeHistory::unpack(&trade_history_info.data.borrow_mut())?;
+
+    // the refund is paid in the mint of the purchase, the pass book mint may have changed since
+    let is_native = cmp_pubkeys(&purchase.mint, &spl_token::native_mint::id());
+    if is_native {
+        assert_account_key(
+            buyer_token_account_info,
+            &purchase.buyer,
+            Some(NFTPassError::UserWalletMustMatchUserTokenAccount),
+        )?;
+    } else {
+        let buyer_token_account = Account::unpack(&buyer_token_account_info.data.borrow())?;
+        if buyer_token_account.owner != purchase.buyer
+            || buyer_token_account.mint != purchase.mint
+        {
+            return Err(NFTPassError::UserWalletMustMatchUserTokenAccount.into());
+        }
+    }
+
+    if passbook.escrow_hold_period.is_some() {
+        // the refund window ends before the escrow can be settled, the revenue is still there
+        let escrow_info = next_account_info(account_info_iter)?;
+        let escrow_token_info = next_account_info(account_info_iter)?;
+        escrow_transfer(
+            program_id,
+            &passbook,
+            pass_book_info,
+            escrow_info,
+            escrow_token_info,
+            buyer_token_account_info,
+            purchase.price,
+        )?;
+        passbook.withdraw_escrow(
+            purchase.price,
+            store.is_referral_active(purchase.purchased_at),
+            purchase.purchased_at,
+        )?;
+    } else {
+        // take back from every payout account what the purchase paid into it
+        for purchase_payout in purchase.payouts.iter() {
+            let payout_info = next_account_info(account_info_iter)?;
+            let payout_token_info = next_account_info(account_info_iter)?;
+            refund_payout(
+                program_id,
+                &purchase.mint,
+                purchase_payout,
+                payout_info,
+                payout_token_info,
+                buyer_token_account_info,
+                rent,
+                is_native,
+            )?;
+        }
+    }
+
+    membership.state = MembershipState::Expired;
+    membership.purchase = None;
+    membership.subscription = None;
+    store.decrement_active_membership_count()?;
+    store.decrement_pass_count()?;
+    trade_history.decrement_already_bought()?;
+    passbook.decrement_supply()?;
+
+    PassBook::pack(passbook, *pass_book_info.data.borrow_mut())?;
+    Store::pack(store, *store_info.data.borrow_mut())?;
+    TradeHistory::pack(trade_history, *trade_history_info.data.borrow_mut())?;
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Move the amount the purchase paid into the payout account back to the buyer
+#[allow(clippy::too_many_arguments)]
+fn refund_payout<'a>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    purchase_payout: &PurchasePayout,
+    payout_info: &AccountInfo<'a>,
+    payout_token_info: &AccountInfo<'a>,
+    buyer_token_account_info: &AccountInfo<'a>,
+    rent: &Rent,
+    is_native: bool,
+) -> Result<(), ProgramError> {
+    let mut payout = load_payout_account(
+        &purchase_payout.authority,
+        mint,
+        payout_info,
+        payout_token_info,
+    )?;
+    let amount = purchase_payout.amount;
+    payout.refund(amount)?;
+
+    let (_, payout_bump_seed) =
+        find_payout_program_address(program_id, &payout.authority, &payout.mint);
+
+    if is_native {
+        // the payout account holds the lamports and has to stay rent exempt
+        let remaining = payout_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(NFTPassError::InsufficientPayoutBalance)?;
+        if remaining < rent.minimum_balance(payout_info.data_len()) {
+            return Err(NFTPassError::InsufficientPayoutBalance.into());
+        }
+        **payout_info.try_borrow_mut_lamports()? = remaining;
+        **buyer_token_account_info.try_borrow_mut_lamports()? = buyer_token_account_info
+            .lamports()
+            .checked_add(amount)
+            .ok_or(NFTPassError::MathOverflow)?;
+    } else {
+        let payout_signer_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &payout.authority.to_bytes(),
+            &payout.mint.to_bytes(),
+            Payout::PREFIX.as_bytes(),
+            &[payout_bump_seed],
+        ];
+
+        spl_token_transfer(
+            payout_token_info.clone(),
+            buyer_token_account_info.clone(),
+            payout_info.clone(),
+            amount,
+            &[payout_signer_seeds],
+        )?;
+    }
+
+    Payout::pack(payout, *payout_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! RemoveStoreAuthority instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_pass_store_program_address, find_store_authority_program_address,
+    state::StoreAuthority,
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_memory::sol_memset,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process RemoveStoreAuthority instruction
+pub fn remove_store_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_authority_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(store_authority_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, authority_info.key);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let (store_authority_key, _) =
+        find_store_authority_program_address(program_id, store_info.key, user_info.key);
+    assert_account_key(
+        store_authority_info,
+        &store_authority_key,
+        Some(NFTPassError::InvalidStoreAuthorityKey),
+    )?;
+
+    // make sure the record is initialized before closing it
+    StoreAuthority::unpack(&store_authority_info.data.borrow_mut())?;
+
+    empty_account_balance(store_authority_info, authority_info)?;
+    sol_memset(
+        *store_authority_info.data.borrow_mut(),
+        0,
+        StoreAuthority::LEN,
+    );
+
+    Ok(())
+}
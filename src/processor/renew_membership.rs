@@ -0,0 +1,146 @@
+//! RenewMembership instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_membership_program_address, find_pass_store_program_address,
+    find_trade_history_program_address, id,
+    instruction::BuyPassArgs,
+    processor::buy_pass_book::{
+        assert_purchase_terms, assert_user_token_account, collect_payment,
+        get_or_create_trade_history, update_pass_expiry,
+    },
+    state::{Membership, PassBook, Store, TradeHistory, PREFIX},
+    utils::*,
+};
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Process RenewMembership instruction
+pub fn renew_membership<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: BuyPassArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let user_wallet_info = next_account_info(account_info_iter)?;
+    let user_token_account_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let trade_history_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+
+    let clock_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(pass_book_info, &id())?;
+    assert_owned_by(store_info, &id())?;
+    assert_owned_by(membership_info, &id())?;
+    assert_signer(user_wallet_info)?;
+
+    let mut passbook = PassBook::unpack(&pass_book_info.data.borrow_mut())?;
+    passbook.assert_activated()?;
+    assert_purchase_terms(&passbook, args.expected_price, &args.expected_mint)?;
+    passbook.assert_on_sale(clock.unix_timestamp as u64)?;
+
+    let mut pass_store = Store::unpack(&store_info.data.borrow_mut())?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, &passbook.authority);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    assert_user_token_account(&passbook, user_wallet_info, user_token_account_info)?;
+
+    let (membership_key, _) =
+        find_membership_program_address(program_id, &store_key, user_wallet_info.key);
+    assert_account_key(
+        membership_info,
+        &membership_key,
+        Some(NFTPassError::InvalidMembershipKey),
+    )?;
+
+    let mut membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+
+    if membership.passbook != Some(*pass_book_info.key) {
+        return Err(NFTPassError::InvalidPassBookKey.into());
+    }
+
+    // only live memberships can be renewed, expired ones are bought again
+    let now = clock.unix_timestamp as u64;
+    membership.assert_active(now)?;
+
+    let (trade_history_key, trade_history_bump_seed) =
+        find_trade_history_program_address(program_id, pass_book_info.key, user_wallet_info.key);
+    assert_account_key(
+        trade_history_info,
+        &trade_history_key,
+        Some(NFTPassError::InvalidTradeHistoryKey),
+    )?;
+
+    let trade_history_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &pass_book_info.key.to_bytes(),
+        &user_wallet_info.key.to_bytes(),
+        TradeHistory::PREFIX.as_bytes(),
+        &[trade_history_bump_seed],
+    ];
+
+    let mut trade_history = get_or_create_trade_history(
+        program_id,
+        trade_history_info,
+        pass_book_info,
+        user_wallet_info,
+        payer_account_info,
+        rent_info,
+        system_account_info,
+        trade_history_signer_seeds,
+    )?;
+
+    passbook.assert_wallet_can_buy(trade_history.already_bought)?;
+    passbook.assert_allowlisted(
+        user_wallet_info.key,
+        args.allowlist_proof.as_ref(),
+        trade_history.already_bought,
+    )?;
+
+    membership.renew(passbook.access, passbook.max_uses, now)?;
+    // a renewed membership can't be refunded anymore
+    membership.purchase = None;
+    passbook.record_member_expiry(membership.expires_at);
+
+    collect_payment(
+        program_id,
+        &mut passbook,
+        pass_book_info,
+        &pass_store,
+        user_wallet_info,
+        user_token_account_info,
+        payer_account_info,
+        rent_info,
+        clock,
+        account_info_iter,
+        &[],
+    )?;
+
+    // the pass account, if any, follows the token program
+    let token_program_info = next_account_info(account_info_iter)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    update_pass_expiry(program_id, &membership, account_info_iter)?;
+
+    // pass_count counts sales, a renewal is one but does not take a membership from max_supply
+    pass_store.increment_pass_count()?;
+    trade_history.increment_already_bought()?;
+    PassBook::pack(passbook, *pass_book_info.data.borrow_mut())?;
+    Store::pack(pass_store, *store_info.data.borrow_mut())?;
+    TradeHistory::pack(trade_history, *trade_history_info.data.borrow_mut())?;
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! ResumeMembership instruction processing
+
+use crate::{
+    error::NFTPassError,
+    processor::buy_pass_book::update_pass_expiry,
+    state::{Membership, PassBook},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Process ResumeMembership instruction
+pub fn resume_membership(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let user_wallet_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(pass_book_info, program_id)?;
+    assert_owned_by(membership_info, program_id)?;
+    assert_signer(user_wallet_info)?;
+
+    let mut passbook = PassBook::unpack(&pass_book_info.data.borrow())?;
+    let mut membership = Membership::unpack(&membership_info.data.borrow())?;
+
+    if !cmp_pubkeys(user_wallet_info.key, &membership.owner) {
+        return Err(NFTPassError::InvalidMembershipAuthority.into());
+    }
+    if membership.passbook != Some(*pass_book_info.key) {
+        return Err(NFTPassError::InvalidPassBookKey.into());
+    }
+
+    membership.resume(clock.unix_timestamp as u64)?;
+    passbook.record_member_expiry(membership.expires_at);
+    update_pass_expiry(program_id, &membership, account_info_iter)?;
+
+    PassBook::pack(passbook, *pass_book_info.data.borrow_mut())?;
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! RevokeUseAuthority instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_membership_program_address, find_use_authority_program_address,
+    state::{Membership, UseAuthority},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_memory::sol_memset,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process RevokeUseAuthority instruction
+pub fn revoke_use_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let use_authority_record_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(use_authority_record_info, program_id)?;
+    assert_signer(owner_info)?;
+
+    if membership_info.data_is_empty() {
+        // the membership was closed when it moved to a new pass holder,
+        // its previous owner is the wallet it was derived from
+        let store_info = next_account_info(account_info_iter)?;
+        let (membership_key, _) =
+            find_membership_program_address(program_id, store_info.key, owner_info.key);
+        assert_account_key(
+            membership_info,
+            &membership_key,
+            Some(NFTPassError::InvalidMembershipAuthority),
+        )?;
+    } else {
+        assert_owned_by(membership_info, program_id)?;
+        let membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+
+        assert_account_key(
+            owner_info,
+            &membership.owner,
+            Some(NFTPassError::InvalidMembershipAuthority),
+        )?;
+    }
+
+    let (use_authority_key, _) =
+        find_use_authority_program_address(program_id, membership_info.key, user_info.key);
+    assert_account_key(
+        use_authority_record_info,
+        &use_authority_key,
+        Some(NFTPassError::InvalidUseAuthorityKey),
+    )?;
+
+    // make sure the record is initialized before closing it
+    UseAuthority::unpack(&use_authority_record_info.data.borrow_mut())?;
+
+    empty_account_balance(use_authority_record_info, owner_info)?;
+    sol_memset(
+        *use_authority_record_info.data.borrow_mut(),
+        0,
+        UseAuthority::LEN,
+    );
+
+    Ok(())
+}
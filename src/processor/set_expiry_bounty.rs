@@ -0,0 +1,37 @@
+//! SetExpiryBounty instruction processing
+
+use crate::{
+    error::NFTPassError, find_pass_store_program_address, instruction::SetExpiryBountyArgs,
+    state::Store, utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process SetExpiryBounty instruction
+pub fn set_expiry_bounty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetExpiryBountyArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(store_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    let (store_key, _) = find_pass_store_program_address(program_id, authority_info.key);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let mut store = Store::unpack(&store_info.data.borrow_mut())?;
+
+    store.expiry_bounty = args.bounty;
+
+    Store::pack(store, *store_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! SetMarketConfig instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_market_config_program_address,
+    instruction::SetMarketConfigArgs,
+    state::{MarketConfig, PREFIX},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process SetMarketConfig instruction
+pub fn set_market_config<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetMarketConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let market_config_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+
+    assert_signer(authority_info)?;
+
+    let (market_config_key, market_config_bump_seed) =
+        find_market_config_program_address(program_id, authority_info.key);
+    assert_account_key(
+        market_config_info,
+        &market_config_key,
+        Some(NFTPassError::InvalidMarketConfigKey),
+    )?;
+
+    let unpack = MarketConfig::unpack(&market_config_info.data.borrow_mut());
+    let mut market_config = match unpack {
+        Ok(market_config) => market_config,
+        Err(_) => {
+            assert_signer(payer_info)?;
+            let market_config_signer_seeds = &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                &authority_info.key.to_bytes(),
+                MarketConfig::PREFIX.as_bytes(),
+                &[market_config_bump_seed],
+            ];
+            create_or_allocate_account_raw(
+                *program_id,
+                market_config_info,
+                rent_info,
+                system_account_info,
+                payer_info,
+                MarketConfig::LEN,
+                market_config_signer_seeds,
+            )?;
+            msg!("New market config account was created");
+            let mut market_config =
+                MarketConfig::unpack_unchecked(&market_config_info.data.borrow_mut())?;
+            market_config.init(*authority_info.key, market_config_bump_seed);
+            market_config
+        }
+    };
+
+    market_config.set_fees(
+        args.market_fee_basis_point,
+        args.referral_share,
+        args.referral_kick_back_share,
+    )?;
+
+    MarketConfig::pack(market_config, *market_config_info.data.borrow_mut())?;
+
+    Ok(())
+}
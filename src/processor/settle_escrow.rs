@@ -0,0 +1,106 @@
+//! SettleEscrow instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_pass_store_program_address,
+    math::SafeMath,
+    processor::buy_pass_book::{calculate_payouts, escrow_transfer, load_payout_account},
+    state::{PassBook, Payout, Store},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Process SettleEscrow instruction
+pub fn settle_escrow<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let escrow_info = next_account_info(account_info_iter)?;
+    let escrow_token_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(pass_book_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    assert_account_key(system_program_info, &system_program::id(), None)?;
+
+    let mut passbook = PassBook::unpack(&pass_book_info.data.borrow_mut())?;
+    if passbook.escrow_hold_period.is_none() {
+        return Err(NFTPassError::EscrowNotEnabled.into());
+    }
+    if !passbook.has_escrow_balance() {
+        return Err(NFTPassError::EscrowEmpty.into());
+    }
+    // only revenue held for the whole hold period is paid out, later purchases wait for their own release
+    let (released_amount, released_referral_amount) =
+        passbook.take_released_escrow(clock.unix_timestamp as u64)?;
+    if released_amount == 0 && released_referral_amount == 0 {
+        return Err(NFTPassError::EscrowLocked.into());
+    }
+
+    let (store_key, _) = find_pass_store_program_address(program_id, &passbook.authority);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+    let store = Store::unpack(&store_info.data.borrow())?;
+
+    // revenue of purchases with and without an active referral is split separately
+    let payout_accounts = account_info_iter.as_slice();
+    let held_amounts = [
+        (released_referral_amount, true),
+        (released_amount, false),
+    ];
+    for (held_amount, referral_active) in held_amounts {
+        if held_amount == 0 {
+            continue;
+        }
+        let mut payouts = calculate_payouts(
+            &passbook,
+            &store,
+            held_amount,
+            referral_active,
+            &mut payout_accounts.iter(),
+        )?;
+        // rounding dust and a referrer share without referrer go to the first payout account,
+        // creators come first, nothing is left in the escrow
+        let paid_amount = payouts
+            .iter()
+            .try_fold(0u64, |total, (_, amount)| total.error_add(*amount))?;
+        if let Some((_, first_amount)) = payouts.first_mut() {
+            *first_amount = first_amount.error_add(held_amount.error_sub(paid_amount)?)?;
+        }
+        for (payout_info, amount) in payouts.iter() {
+            let mut payout = load_payout_account(
+                &payout_info.authority,
+                &passbook.mint,
+                payout_info.payout_account,
+                payout_info.token_account,
+            )?;
+            if *amount > 0 {
+                escrow_transfer(
+                    program_id,
+                    &passbook,
+                    pass_book_info,
+                    escrow_info,
+                    escrow_token_info,
+                    payout_info.token_account,
+                    *amount,
+                )?;
+            }
+            payout.cash_in = payout.cash_in.error_add(*amount)?;
+            Payout::pack(payout, *payout_info.payout_account.data.borrow_mut())?;
+        }
+    }
+
+    PassBook::pack(passbook, *pass_book_info.data.borrow_mut())?;
+
+    Ok(())
+}
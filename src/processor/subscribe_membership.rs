@@ -0,0 +1,90 @@
+//! SubscribeMembership instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_membership_program_address, find_pass_store_program_address,
+    instruction::SubscribeArgs,
+    math::SafeMath,
+    processor::buy_pass_book::{assert_purchase_terms, assert_user_token_account},
+    state::{Membership, PassBook, Subscription},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Process SubscribeMembership instruction
+pub fn subscribe_membership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SubscribeArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let user_wallet_info = next_account_info(account_info_iter)?;
+    let user_token_account_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(pass_book_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_owned_by(membership_info, program_id)?;
+    assert_signer(user_wallet_info)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+
+    let passbook = PassBook::unpack(&pass_book_info.data.borrow())?;
+    assert_purchase_terms(&passbook, args.expected_price, &args.expected_mint)?;
+    if passbook.access.is_none() {
+        return Err(NFTPassError::SubscriptionRequiresAccess.into());
+    }
+    // native SOL can't be delegated
+    if cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id()) {
+        return Err(NFTPassError::SubscriptionRequiresToken.into());
+    }
+    if args.periods == 0 {
+        return Err(NFTPassError::InvalidSubscriptionPeriods.into());
+    }
+
+    let (store_key, _) = find_pass_store_program_address(program_id, &passbook.authority);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let (membership_key, _) =
+        find_membership_program_address(program_id, &store_key, user_wallet_info.key);
+    assert_account_key(
+        membership_info,
+        &membership_key,
+        Some(NFTPassError::InvalidMembershipKey),
+    )?;
+
+    let mut membership = Membership::unpack(&membership_info.data.borrow())?;
+    if membership.passbook != Some(*pass_book_info.key) {
+        return Err(NFTPassError::InvalidPassBookKey.into());
+    }
+    membership.assert_active(clock.unix_timestamp as u64)?;
+
+    assert_user_token_account(&passbook, user_wallet_info, user_token_account_info)?;
+
+    // the membership is the delegate charging every period
+    spl_token_approve(
+        user_token_account_info.clone(),
+        membership_info.clone(),
+        user_wallet_info.clone(),
+        passbook.price.error_mul(args.periods)?,
+    )?;
+
+    membership.subscription = Some(Subscription {
+        token_account: *user_token_account_info.key,
+        price: passbook.price,
+        periods_left: args.periods,
+    });
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+//! SyncMembershipOwner instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_membership_program_address, find_pass_program_address, find_program_authority,
+    processor::buy_pass_book::{get_or_create_membership, release_pass_book_member},
+    state::{Membership, MembershipState, Pass, PassBook, Store, PREFIX},
+    utils::*,
+};
+use mpl_token_metadata::pda::find_master_edition_account;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_memory::sol_memset,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+use spl_token::state::Account;
+use std::slice::Iter;
+
+/// Process SyncMembershipOwner instruction
+pub fn sync_membership_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let holder_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let previous_membership_info = next_account_info(account_info_iter)?;
+    let membership_info = next_account_info(account_info_iter)?;
+    let previous_owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(pass_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_owned_by(previous_membership_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_signer(payer_info)?;
+
+    let mut pass = Pass::unpack(&pass_info.data.borrow_mut())?;
+    let (pass_key, _) = find_pass_program_address(program_id, &pass.mint);
+    assert_account_key(pass_info, &pass_key, Some(NFTPassError::InvalidPassKey))?;
+
+    // the holder of the pass edition owns the membership
+    let token_account = Account::unpack(&token_account_info.data.borrow())?;
+    if token_account.mint != pass.mint || token_account.amount != 1 {
+        return Err(NFTPassError::NotPassHolder.into());
+    }
+    assert_account_key(
+        holder_info,
+        &token_account.owner,
+        Some(NFTPassError::NotPassHolder),
+    )?;
+    if cmp_pubkeys(holder_info.key, &pass.owner) {
+        return Err(NFTPassError::CantSetTheSameValue.into());
+    }
+    assert_account_key(previous_owner_info, &pass.owner, None)?;
+    assert_account_key(
+        pass_book_info,
+        &pass.pass_book,
+        Some(NFTPassError::InvalidPassBookKey),
+    )?;
+
+    // nothing is left to enforce once the pass book is deleted
+    let soulbound = if pass_book_info.data_is_empty() {
+        false
+    } else {
+        assert_owned_by(pass_book_info, program_id)?;
+        PassBook::unpack(&pass_book_info.data.borrow())?.soulbound
+    };
+
+    // a soulbound pass thawed for its transfer is frozen again in the holder wallet
+    if soulbound && !token_account.is_frozen() {
+        refreeze_pass(
+            program_id,
+            &pass.mint,
+            token_account_info,
+            holder_info,
+            account_info_iter,
+        )?;
+    }
+
+    let previous_membership = Membership::unpack(&previous_membership_info.data.borrow_mut())?;
+    let (previous_membership_key, _) =
+        find_membership_program_address(program_id, &previous_membership.store, &pass.owner);
+    assert_account_key(
+        previous_membership_info,
+        &previous_membership_key,
+        Some(NFTPassError::InvalidMembershipKey),
+    )?;
+    if previous_membership.pass != Some(pass_key)
+        || previous_membership.passbook != Some(pass.pass_book)
+    {
+        return Err(NFTPassError::InvalidMembershipKey.into());
+    }
+    assert_account_key(
+        store_info,
+        &previous_membership.store,
+        Some(NFTPassError::InvalidStoreKey),
+    )?;
+
+    let mut store = Store::unpack(&store_info.data.borrow_mut())?;
+
+    let (membership_key, membership_bump_seed) =
+        find_membership_program_address(program_id, store_info.key, holder_info.key);
+    assert_account_key(
+        membership_info,
+        &membership_key,
+        Some(NFTPassError::InvalidMembershipKey),
+    )?;
+
+    let membership_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &store_info.key.to_bytes(),
+        &holder_info.key.to_bytes(),
+        Membership::PREFIX.as_bytes(),
+        &[membership_bump_seed],
+    ];
+
+    let (mut membership, is_new_membership) = get_or_create_membership(
+        program_id,
+        membership_info,
+        holder_info,
+        store_info,
+        payer_info,
+        rent_info,
+        system_program_info,
+        membership_signer_seeds,
+    )?;
+
+    // the holder membership is replaced, only an exhausted one can be
+    if !is_new_membership {
+        if membership.state == MembershipState::Activated {
+            if !membership.is_exhausted(clock.unix_timestamp as u64) {
+                return Err(NFTPassError::UserHasActiveMembership.into());
+            }
+            store.decrement_active_membership_count()?;
+        }
+        store.decrement_membership_count()?;
+        if let Some(replaced_passbook) = membership.passbook {
+            release_pass_book_member(
+                program_id,
+                &replaced_passbook,
+                next_account_info(account_info_iter)?,
+            )?;
+        }
+    }
+
+    membership.owner = *holder_info.key;
+    membership.state = previous_membership.state;
+    membership.passbook = previous_membership.passbook;
+    membership.pass = previous_membership.pass;
+    membership.expires_at = previous_membership.expires_at;
+    membership.activated_at = previous_membership.activated_at;
+    membership.uses = previous_membership.uses;
+    membership.purchase = previous_membership.purchase;
+    membership.paused_at = previous_membership.paused_at;
+    membership.pause_ends_at = previous_membership.pause_ends_at;
+    membership.paused_duration = previous_membership.paused_duration;
+    // the subscription was delegated by the previous owner
+    membership.subscription = None;
+
+    pass.owner = *holder_info.key;
+    pass.expires_at = membership.expires_at;
+
+    // close the membership of the previous owner
+    sol_memset(
+        *previous_membership_info.data.borrow_mut(),
+        0,
+        Membership::LEN,
+    );
+    empty_account_balance(previous_membership_info, previous_owner_info)?;
+
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+    Pass::pack(pass, *pass_info.data.borrow_mut())?;
+    Store::pack(store, *store_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+/// Freeze the pass in the holder token account with the program authority as delegate
+fn refreeze_pass<'a>(
+    program_id: &Pubkey,
+    pass_mint: &Pubkey,
+    token_account_info: &AccountInfo<'a>,
+    holder_info: &AccountInfo<'a>,
+    account_info_iter: &mut Iter<AccountInfo<'a>>,
+) -> ProgramResult {
+    let program_authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(holder_info)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    assert_account_key(token_metadata_program_info, &mpl_token_metadata::id(), None)?;
+    assert_account_key(mint_info, pass_mint, Some(NFTPassError::InvalidMintKey))?;
+    assert_account_key(
+        edition_info,
+        &find_master_edition_account(pass_mint).0,
+        Some(NFTPassError::InvalidMintKey),
+    )?;
+
+    let (program_authority_key, program_authority_bump_seed) = find_program_authority(program_id);
+    assert_account_key(
+        program_authority_info,
+        &program_authority_key,
+        Some(NFTPassError::InvalidProgramAuthority),
+    )?;
+
+    let program_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &[program_authority_bump_seed],
+    ];
+
+    spl_token_approve(
+        token_account_info.clone(),
+        program_authority_info.clone(),
+        holder_info.clone(),
+        1,
+    )?;
+    mpl_freeze_delegated_account(
+        program_authority_info,
+        token_account_info,
+        edition_info,
+        mint_info,
+        token_program_info,
+        program_authority_signer_seeds,
+    )
+}
@@ -0,0 +1,87 @@
+//! ThawPass instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_pass_program_address, find_program_authority,
+    state::{Pass, PassBook, PREFIX},
+    utils::*,
+};
+use mpl_token_metadata::pda::find_master_edition_account;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use spl_token::state::Account;
+
+/// Process ThawPass instruction
+pub fn thaw_pass(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pass_info = next_account_info(account_info_iter)?;
+    let pass_book_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let program_authority_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(pass_info, program_id)?;
+    assert_signer(authority_info)?;
+    assert_account_key(token_program_info, &spl_token::id(), None)?;
+    assert_account_key(token_metadata_program_info, &mpl_token_metadata::id(), None)?;
+
+    let pass = Pass::unpack(&pass_info.data.borrow())?;
+    let (pass_key, _) = find_pass_program_address(program_id, mint_info.key);
+    assert_account_key(pass_info, &pass_key, Some(NFTPassError::InvalidPassKey))?;
+    assert_account_key(
+        pass_book_info,
+        &pass.pass_book,
+        Some(NFTPassError::InvalidPassBookKey),
+    )?;
+    assert_account_key(
+        edition_info,
+        &find_master_edition_account(mint_info.key).0,
+        Some(NFTPassError::InvalidMintKey),
+    )?;
+
+    if pass_book_info.data_is_empty() {
+        // the pass is no longer soulbound once its pass book is deleted, the holder thaws it
+        let token_account = Account::unpack(&token_account_info.data.borrow())?;
+        if token_account.mint != *mint_info.key || token_account.owner != *authority_info.key {
+            return Err(NFTPassError::NotPassHolder.into());
+        }
+    } else {
+        assert_owned_by(pass_book_info, program_id)?;
+        let pass_book = PassBook::unpack(&pass_book_info.data.borrow())?;
+        assert_account_key(
+            authority_info,
+            &pass_book.authority,
+            Some(NFTPassError::InvalidCreatorKey),
+        )?;
+    }
+
+    let (program_authority_key, program_authority_bump_seed) = find_program_authority(program_id);
+    assert_account_key(
+        program_authority_info,
+        &program_authority_key,
+        Some(NFTPassError::InvalidProgramAuthority),
+    )?;
+
+    let program_authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        &[program_authority_bump_seed],
+    ];
+
+    mpl_thaw_delegated_account(
+        program_authority_info,
+        token_account_info,
+        edition_info,
+        mint_info,
+        token_program_info,
+        program_authority_signer_seeds,
+    )
+}
@@ -0,0 +1,55 @@
+//! TopUpStoreAuthority instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_pass_store_program_address, find_store_authority_program_address,
+    instruction::TopUpStoreAuthorityArgs,
+    state::StoreAuthority,
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Process TopUpStoreAuthority instruction
+pub fn top_up_store_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: TopUpStoreAuthorityArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_authority_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let user_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(store_authority_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    if args.additional_redemptions == 0 {
+        return Err(NFTPassError::WrongRedemptionCount.into());
+    }
+
+    let (store_key, _) = find_pass_store_program_address(program_id, authority_info.key);
+    assert_account_key(store_info, &store_key, Some(NFTPassError::InvalidStoreKey))?;
+
+    let (store_authority_key, _) =
+        find_store_authority_program_address(program_id, store_info.key, user_info.key);
+    assert_account_key(
+        store_authority_info,
+        &store_authority_key,
+        Some(NFTPassError::InvalidStoreAuthorityKey),
+    )?;
+
+    let mut store_authority = StoreAuthority::unpack(&store_authority_info.data.borrow_mut())?;
+
+    store_authority.add_allowed_redemptions(args.additional_redemptions)?;
+
+    StoreAuthority::pack(store_authority, *store_authority_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! UseMembership instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_store_authority_program_address, find_use_authority_program_address,
+    instruction::UseMembershipArgs,
+    state::{Membership, MembershipState, Store, StoreAuthority, UseAuthority},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Process UseMembership instruction
+pub fn use_membership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UseMembershipArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let membership_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(clock_info)?;
+
+    assert_owned_by(membership_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    let mut membership = Membership::unpack(&membership_info.data.borrow_mut())?;
+    let mut store = Store::unpack(&store_info.data.borrow_mut())?;
+
+    assert_account_key(
+        store_info,
+        &membership.store,
+        Some(NFTPassError::InvalidStoreKey),
+    )?;
+
+    membership.assert_active(clock.unix_timestamp as u64)?;
+
+    if !cmp_pubkeys(authority_info.key, &membership.owner) {
+        // a delegate must present its use authority or store authority record
+        let authority_record_info = next_account_info(account_info_iter)
+            .map_err(|_| NFTPassError::InvalidMembershipAuthority)?;
+
+        assert_owned_by(authority_record_info, program_id)?;
+
+        let (use_authority_key, _) =
+            find_use_authority_program_address(program_id, membership_info.key, authority_info.key);
+        let (store_authority_key, _) =
+            find_store_authority_program_address(program_id, store_info.key, authority_info.key);
+
+        if cmp_pubkeys(authority_record_info.key, &use_authority_key) {
+            let mut use_authority = UseAuthority::unpack(&authority_record_info.data.borrow_mut())?;
+
+            if use_authority.membership != *membership_info.key {
+                return Err(NFTPassError::InvalidUseAuthorityKey.into());
+            }
+
+            use_authority.consume_allowed_uses(args.count)?;
+
+            UseAuthority::pack(use_authority, *authority_record_info.data.borrow_mut())?;
+        } else if cmp_pubkeys(authority_record_info.key, &store_authority_key) {
+            let mut store_authority =
+                StoreAuthority::unpack(&authority_record_info.data.borrow_mut())?;
+
+            if store_authority.store != *store_info.key {
+                return Err(NFTPassError::InvalidStoreAuthorityKey.into());
+            }
+
+            store_authority.consume_allowed_redemptions(args.count)?;
+
+            StoreAuthority::pack(store_authority, *authority_record_info.data.borrow_mut())?;
+        } else {
+            return Err(NFTPassError::InvalidUseAuthorityKey.into());
+        }
+    }
+
+    if membership.consume_uses(args.count)? {
+        membership.state = MembershipState::Expired;
+        store.decrement_active_membership_count()?;
+    }
+
+    store.increment_redemptions_count()?;
+
+    Membership::pack(membership, *membership_info.data.borrow_mut())?;
+    Store::pack(store, *store_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Withdraw instruction processing
+
+use crate::{
+    error::NFTPassError,
+    find_payout_program_address,
+    instruction::WithdrawArgs,
+    state::{Payout, PREFIX},
+    utils::*,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+/// Process Withdraw instruction
+pub fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], args: WithdrawArgs) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payout_info = next_account_info(account_info_iter)?;
+    let treasury_holder_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let rent = &Rent::from_account_info(rent_info)?;
+
+    assert_owned_by(payout_info, program_id)?;
+    assert_signer(authority_info)?;
+
+    let mut payout = Payout::unpack(&payout_info.data.borrow_mut())?;
+
+    assert_account_key(
+        authority_info,
+        &payout.authority,
+        Some(NFTPassError::InvalidCreatorKey),
+    )?;
+
+    let (payout_key, payout_bump_seed) =
+        find_payout_program_address(program_id, &payout.authority, &payout.mint);
+    assert_account_key(payout_info, &payout_key, Some(NFTPassError::InvalidPayoutKey))?;
+    assert_account_key(
+        treasury_holder_info,
+        &payout.treasury_holder,
+        Some(NFTPassError::InvalidTokenAccountKey),
+    )?;
+
+    payout.withdraw(args.amount)?;
+
+    let is_native = cmp_pubkeys(&payout.mint, &spl_token::native_mint::id());
+    if is_native {
+        // the payout account holds the lamports and has to stay rent exempt
+        let remaining = payout_info
+            .lamports()
+            .checked_sub(args.amount)
+            .ok_or(NFTPassError::InsufficientPayoutBalance)?;
+        if remaining < rent.minimum_balance(payout_info.data_len()) {
+            return Err(NFTPassError::InsufficientPayoutBalance.into());
+        }
+        **payout_info.try_borrow_mut_lamports()? = remaining;
+        **destination_info.try_borrow_mut_lamports()? = destination_info
+            .lamports()
+            .checked_add(args.amount)
+            .ok_or(NFTPassError::MathOverflow)?;
+    } else {
+        let token_program_info = next_account_info(account_info_iter)?;
+        if !cmp_pubkeys(token_program_info.key, &spl_token::id()) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let payout_signer_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &payout.authority.to_bytes(),
+            &payout.mint.to_bytes(),
+            Payout::PREFIX.as_bytes(),
+            &[payout_bump_seed],
+        ];
+
+        spl_token_transfer(
+            treasury_holder_info.clone(),
+            destination_info.clone(),
+            payout_info.clone(),
+            args.amount,
+            &[payout_signer_seeds],
+        )?;
+    }
+
+    Payout::pack(payout, *payout_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    borsh::try_from_slice_unchecked,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::{AccountType, MARKET_CONFIG_LENGTH};
+use crate::error::NFTPassError;
+
+/// Fee configuration of a market place, applied to every pass book sold through it
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct MarketConfig {
+    pub account_type: AccountType,       //1
+    pub authority: Pubkey,               //32
+    pub market_fee_basis_point: u16,     //2
+    pub referral_share: u8,              //1
+    pub referral_kick_back_share: u8,    //1
+    pub bump: u8,                        //1
+}
+
+impl MarketConfig {
+    pub const PREFIX: &'static str = "market";
+    /// Initialize a market config
+    pub fn init(&mut self, authority: Pubkey, bump: u8) {
+        self.account_type = AccountType::MarketConfig;
+        self.authority = authority;
+        self.bump = bump;
+    }
+
+    /// Set the fees charged by the market place
+    pub fn set_fees(
+        &mut self,
+        market_fee_basis_point: u16,
+        referral_share: u8,
+        referral_kick_back_share: u8,
+    ) -> Result<(), ProgramError> {
+        if market_fee_basis_point > 10_000 {
+            return Err(NFTPassError::WrongMarketSellerBasisPoint.into());
+        }
+        if referral_share > 100 || referral_kick_back_share > 100 {
+            return Err(NFTPassError::WrongReferralShare.into());
+        }
+        self.market_fee_basis_point = market_fee_basis_point;
+        self.referral_share = referral_share;
+        self.referral_kick_back_share = referral_kick_back_share;
+        Ok(())
+    }
+}
+
+impl IsInitialized for MarketConfig {
+    fn is_initialized(&self) -> bool {
+        self.account_type != AccountType::Uninitialized
+            && self.account_type == AccountType::MarketConfig
+    }
+}
+
+impl Sealed for MarketConfig {}
+
+impl Pack for MarketConfig {
+    const LEN: usize = MARKET_CONFIG_LENGTH;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if (src[0] != AccountType::MarketConfig as u8
+            && src[0] != AccountType::Uninitialized as u8)
+            || src.len() != Self::LEN
+        {
+            msg!("Failed to deserialize");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let result: Self = try_from_slice_unchecked(src)?;
+
+        Ok(result)
+    }
+}
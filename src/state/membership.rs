@@ -1,6 +1,7 @@
 //! Membership definitions
 
 use super::*;
+use crate::{error::NFTPassError, math::SafeMath};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     borsh::try_from_slice_unchecked,
@@ -18,7 +19,12 @@ pub const MAX_MEMBERSHIP_LEN: usize = 1 //account type
 + 9 // expires
 + 1 // memebership state
 + 1 //
-+ USES_LENGTH;
++ USES_LENGTH
++ PURCHASE_LEN // purchase
++ 49 // subscription
++ 9 // paused_at
++ 9 // pause_ends_at
++ 8; // paused_duration
 
 /// Pack state
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -37,6 +43,54 @@ impl Default for MembershipState {
     }
 }
 
+/// Creators, market place and referrer
+pub const MAX_PURCHASE_PAYOUTS: usize = MAX_CREATOR_LIMIT + 2;
+
+pub const PURCHASE_LEN: usize = 1 // option
++ 32 // buyer
++ 8 // price
++ 8 // purchased_at
++ 32 // mint
++ 4 + MAX_PURCHASE_PAYOUTS * (32 + 8); // payouts
+
+/// Amount a purchase paid into a payout account
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema, Default)]
+pub struct PurchasePayout {
+    /// Authority of the payout account
+    pub authority: Pubkey,
+    /// Amount paid
+    pub amount: u64,
+}
+
+/// Purchase that can still be refunded
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema, Default)]
+pub struct Purchase {
+    /// Wallet that paid for the membership
+    pub buyer: Pubkey,
+    /// Price paid
+    pub price: u64,
+    /// Purchase date in unix timestamp
+    pub purchased_at: u64,
+    /// Mint the price was paid in
+    pub mint: Pubkey,
+    /// Amounts paid into the payout accounts, empty when the price went to the escrow
+    pub payouts: Vec<PurchasePayout>,
+}
+
+/// Subscription renewing the membership from the member token account
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema, Default)]
+pub struct Subscription {
+    /// Token account the membership is delegated to charge
+    pub token_account: Pubkey,
+    /// Price agreed for each period
+    pub price: u64,
+    /// Number of periods left to charge
+    pub periods_left: u64,
+}
+
 /// Membership
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema, Default)]
@@ -57,6 +111,16 @@ pub struct Membership {
     pub expires_at: Option<u64>,
     pub activated_at: Option<u64>,
     pub uses: Option<Uses>,
+    /// Last purchase, kept only while the pass book has a refund window
+    pub purchase: Option<Purchase>,
+    /// Subscription charged when the membership expires
+    pub subscription: Option<Subscription>,
+    /// Pause start in unix timestamp, the membership can't be used while paused
+    pub paused_at: Option<u64>,
+    /// End of the longest pause allowed in unix timestamp, the membership resumes on its own afterwards
+    pub pause_ends_at: Option<u64>,
+    /// Seconds paused since the last purchase or renewal, capped by the max pause days of the pass book
+    pub paused_duration: u64,
 }
 
 impl Membership {
@@ -72,6 +136,198 @@ impl Membership {
         self.activated_at = None;
         self.state = MembershipState::NotActivated;
         self.uses = None;
+        self.purchase = None;
+        self.subscription = None;
+        self.paused_at = None;
+        self.pause_ends_at = None;
+        self.paused_duration = 0;
+    }
+
+    /// Check if membership is activated and not expired
+    pub fn assert_active(&self, now: u64) -> Result<(), ProgramError> {
+        if self.state != MembershipState::Activated {
+            return Err(NFTPassError::MembershipNotActivated.into());
+        }
+
+        if self.is_paused(now) {
+            return Err(NFTPassError::MembershipPaused.into());
+        }
+
+        if let Some(expires_at) = self.effective_expires_at(now) {
+            if now > expires_at {
+                return Err(NFTPassError::MembershipExpired.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the clock of the membership is stopped, a pause run over its cap has ended on its own
+    pub fn is_paused(&self, now: u64) -> bool {
+        match (self.paused_at, self.pause_ends_at) {
+            (Some(_), Some(pause_ends_at)) => now <= pause_ends_at,
+            _ => false,
+        }
+    }
+
+    /// Seconds the clock of the membership is stopped for by the current pause
+    fn current_pause_duration(&self, now: u64) -> u64 {
+        match (self.paused_at, self.pause_ends_at) {
+            (Some(paused_at), Some(pause_ends_at)) => now.min(pause_ends_at).saturating_sub(paused_at),
+            _ => 0,
+        }
+    }
+
+    /// Expiry pushed back by the current pause, `None` never expires
+    pub fn effective_expires_at(&self, now: u64) -> Option<u64> {
+        self.expires_at
+            .map(|expires_at| expires_at.saturating_add(self.current_pause_duration(now)))
+    }
+
+    /// Stop the clock of an active membership for what is left of the pause allowance of the period
+    pub fn pause(&mut self, max_pause_duration: u64, now: u64) -> Result<(), ProgramError> {
+        if !self.is_paused(now) {
+            self.end_pause(now)?;
+        }
+        self.assert_active(now)?;
+        let allowance = max_pause_duration.saturating_sub(self.paused_duration);
+        if allowance == 0 {
+            return Err(NFTPassError::PauseAllowanceUsed.into());
+        }
+        self.paused_at = Some(now);
+        self.pause_ends_at = Some(now.error_add(allowance)?);
+        Ok(())
+    }
+
+    /// Restart the clock of a paused membership, its expiry is pushed back by the paused duration
+    pub fn resume(&mut self, now: u64) -> Result<(), ProgramError> {
+        if self.paused_at.is_none() {
+            return Err(NFTPassError::MembershipNotPaused.into());
+        }
+        if self.is_exhausted(now) {
+            return Err(NFTPassError::MembershipExpired.into());
+        }
+        self.end_pause(now)
+    }
+
+    /// Move the expiry by the paused duration and count it against the pause allowance,
+    /// a pause run over its cap ends at its cap
+    fn end_pause(&mut self, now: u64) -> Result<(), ProgramError> {
+        let paused_duration = self.current_pause_duration(now);
+        if let Some(expires_at) = self.expires_at {
+            self.expires_at = Some(expires_at.error_add(paused_duration)?);
+        }
+        self.paused_duration = self.paused_duration.error_add(paused_duration)?;
+        self.paused_at = None;
+        self.pause_ends_at = None;
+        Ok(())
+    }
+
+    /// Check if membership has run out of time or uses
+    pub fn is_exhausted(&self, now: u64) -> bool {
+        let time_expired = match self.effective_expires_at(now) {
+            Some(expires_at) => now > expires_at,
+            None => false,
+        };
+        let uses_exhausted = match &self.uses {
+            Some(uses) => uses.remaining == 0,
+            None => false,
+        };
+
+        time_expired || uses_exhausted
+    }
+
+    /// Check the last purchase can be refunded at the given time, returns it
+    pub fn assert_refundable(
+        &self,
+        refund_window: Option<u64>,
+        now: u64,
+    ) -> Result<&Purchase, ProgramError> {
+        if self.state != MembershipState::Activated {
+            return Err(NFTPassError::MembershipNotActivated.into());
+        }
+        let (purchase, refund_window) = match (&self.purchase, refund_window) {
+            (Some(purchase), Some(refund_window)) => (purchase, refund_window),
+            _ => return Err(NFTPassError::RefundWindowClosed.into()),
+        };
+        if now > purchase.purchased_at.error_add(refund_window)? {
+            return Err(NFTPassError::RefundWindowClosed.into());
+        }
+        if let Some(uses) = &self.uses {
+            if uses.remaining != uses.total {
+                return Err(NFTPassError::MembershipAlreadyUsed.into());
+            }
+        }
+        Ok(purchase)
+    }
+
+    /// Check the membership has a subscription to charge at the given time, returns it
+    pub fn assert_subscription_due(&self, now: u64) -> Result<&Subscription, ProgramError> {
+        if self.state != MembershipState::Activated {
+            return Err(NFTPassError::MembershipNotActivated.into());
+        }
+        let subscription = self
+            .subscription
+            .as_ref()
+            .ok_or(NFTPassError::SubscriptionNotFound)?;
+        if self.is_paused(now) {
+            return Err(NFTPassError::MembershipPaused.into());
+        }
+        match self.effective_expires_at(now) {
+            Some(expires_at) if now >= expires_at => Ok(subscription),
+            _ => Err(NFTPassError::SubscriptionNotDue.into()),
+        }
+    }
+
+    /// Consume uses, returns true if no uses are left afterwards
+    pub fn consume_uses(&mut self, count: u64) -> Result<bool, ProgramError> {
+        if count == 0 {
+            return Err(NFTPassError::WrongUseCount.into());
+        }
+
+        if let Some(uses) = self.uses.as_mut() {
+            if count > uses.remaining {
+                return Err(NFTPassError::NotEnoughUses.into());
+            }
+            uses.remaining = uses.remaining.error_sub(count)?;
+            return Ok(uses.remaining == 0);
+        }
+
+        Ok(false)
+    }
+
+    /// Extend the membership from its current expiry and add uses on top of the remaining ones,
+    /// the new period gets a fresh pause allowance
+    pub fn renew(
+        &mut self,
+        access: Option<u64>,
+        max_uses: Option<u64>,
+        now: u64,
+    ) -> Result<(), ProgramError> {
+        // a pause run over its cap ended before the renewal
+        self.end_pause(now)?;
+        self.paused_duration = 0;
+        if let (Some(expires_at), Some(access)) = (self.expires_at, access) {
+            let period = access
+                .checked_mul(86400)
+                .ok_or(NFTPassError::MathOverflow)?;
+            self.expires_at = Some(expires_at.max(now).error_add(period)?);
+        }
+
+        if let Some(max_uses) = max_uses {
+            self.uses = match self.uses.take() {
+                Some(uses) => Some(Uses {
+                    remaining: uses.remaining.error_add(max_uses)?,
+                    total: uses.total.error_add(max_uses)?,
+                }),
+                None => Some(Uses {
+                    remaining: max_uses,
+                    total: max_uses,
+                }),
+            };
+        }
+
+        Ok(())
     }
 }
 
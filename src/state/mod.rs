@@ -9,6 +9,7 @@ mod payout;
 mod trade_history;
 mod membership;
 mod uses;
+mod market_config;
 
 pub use pass::*;
 pub use store::*;
@@ -17,6 +18,7 @@ pub use payout::*;
 pub use trade_history::*;
 pub use membership::*;
 pub use uses::*;
+pub use market_config::*;
 
 
 /// Global prefix for program addresses
@@ -24,6 +26,8 @@ pub const PREFIX: &str = "passbook";
 
 pub const COLLECTION_MINT: &str = "mint";
 
+pub const ESCROW: &str = "escrow";
+
 pub const STORE: &str = "store";
 
 pub const PASS: &str = "pass";
@@ -43,6 +47,8 @@ pub const USE_AUTHORITY_LENGTH: usize = 50; //8 byte padding
 
 pub const STORE_AUTHORITY_LENGTH: usize = 50; //8 byte padding
 
+pub const MARKET_CONFIG_LENGTH: usize = 46; //8 byte padding
+
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum AccountType {
@@ -63,7 +69,9 @@ pub enum AccountType {
     /// Use authority record
     UseAuthority,
     /// Store authority record
-    StoreAuthority
+    StoreAuthority,
+    /// Market place fee configuration
+    MarketConfig,
 }
 
 impl Default for AccountType {
@@ -57,6 +57,8 @@ pub struct InitPassParams {
     pub owner: Pubkey,
     /// Pass book this bass belongs to
     pub pass_book: Pubkey, 
+    /// Mint of the printed edition
+    pub mint: Pubkey,
     /// pass expiration in unix timestamp
     pub expires_at: Option<u64>
 }
@@ -72,6 +74,8 @@ pub struct Pass {
     pub owner: Pubkey,
     /// Pass book
     pub pass_book: Pubkey,
+    /// Mint of the printed edition
+    pub mint: Pubkey,
     /// Description
     pub description: String,
     /// Link to pass image
@@ -84,6 +88,21 @@ pub struct Pass {
     pub state: PassState 
 }
 
+/// Accounts needed to print a pass edition on purchase
+#[derive(Debug)]
+pub struct PassMintArgs {
+    /// Mint of the new edition, the buyer is its mint authority and holds its single token
+    pub mint: Pubkey,
+    /// Mint of the master edition vaulted by the pass book
+    pub master_edition_mint: Pubkey,
+    /// Edition number to print
+    pub edition: u64,
+    /// Token account of the buyer holding the new mint token
+    pub token_account: Pubkey,
+    /// Collection of the store, if it has one
+    pub collection_mint: Option<Pubkey>,
+}
+
 
 impl Pass {
     pub const PREFIX: &'static str = "pass";
@@ -91,6 +110,7 @@ impl Pass {
     pub fn init(&mut self, params: InitPassParams) {
         self.account_type = AccountType::Pass;
         self.pass_book = params.pass_book;
+        self.mint = params.mint;
         self.owner = params.owner;
         self.description = params.description;
         self.uri = params.uri;
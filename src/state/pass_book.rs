@@ -3,6 +3,8 @@
 use super::*;
 use crate::{
     error::NFTPassError,
+    instruction::AllowlistProof,
+    merkle::{allowlist_leaf, verify_proof},
     state::{MAX_DESCRIPTION_LEN, MAX_NAME_LENGTH, MAX_URI_LENGTH},
     math::SafeMath
 };
@@ -33,7 +35,52 @@ pub const MAX_PASS_BOOK_LEN: usize = 1 //account type
 + 32 // mint
 + 33 // market authority
 + 1
-+ 4;
++ 4
++ 4 + MAX_CREATOR_LIMIT * MAX_CREATOR_LEN // creators
++ 9 // max_per_wallet
++ 9 // sale_start
++ 9 // sale_end
++ 33 // allowlist_root
++ 33 // master_edition_mint
++ 8 // members_expire_at
++ 1 // soulbound
++ 9 // refund_window
++ 9 // escrow_hold_period
++ 4 + MAX_ESCROW_BUCKETS * ESCROW_BUCKET_LEN // escrow_buckets
++ 9 // max_pause_days
++ 8; // member_count
+
+/// Maximum number of creators sharing the revenue of a pass book
+pub const MAX_CREATOR_LIMIT: usize = 5;
+
+pub const MAX_CREATOR_LEN: usize = 32 + 1;
+
+/// Maximum number of release times the escrow of a pass book holds revenue for
+pub const MAX_ESCROW_BUCKETS: usize = 8;
+
+pub const ESCROW_BUCKET_LEN: usize = 8 + 8 + 8;
+
+/// Creator receiving a share of the pass book sales
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Creator {
+    /// Creator wallet
+    pub address: Pubkey,
+    /// Percentage of the creators amount paid to this creator
+    pub share: u8,
+}
+
+/// Revenue held in escrow until the same release time
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema, Default)]
+pub struct EscrowBucket {
+    /// Revenue from purchases without an active referral
+    pub amount: u64,
+    /// Revenue from purchases with an active referral
+    pub referral_amount: u64,
+    /// Unix timestamp after which the bucket can be settled
+    pub release_at: u64,
+}
 
 
 /// Pass state
@@ -80,6 +127,55 @@ pub struct InitPassBook {
     pub mint: Pubkey,
     /// market authority
     pub market_authority: Option<Pubkey>, 
+    /// creators sharing the sales revenue
+    pub creators: Vec<Creator>,
+    /// maximum number of passes a single wallet can buy
+    pub max_per_wallet: Option<u64>,
+    /// date the sale opens
+    pub sale_start: Option<u64>,
+    /// date the sale closes
+    pub sale_end: Option<u64>,
+    /// merkle root of the wallets allowed to buy
+    pub allowlist_root: Option<[u8; 32]>,
+    /// mint of the vaulted master edition
+    pub master_edition_mint: Option<Pubkey>,
+    /// passes can't be transferred
+    pub soulbound: bool,
+    /// seconds after a purchase during which it can be refunded
+    pub refund_window: Option<u64>,
+    /// seconds the revenue is held in escrow
+    pub escrow_hold_period: Option<u64>,
+    /// days a membership can be paused for
+    pub max_pause_days: Option<u64>,
+}
+
+/// Master edition vaulted by a pass book at initialization
+#[derive(Debug)]
+pub struct MasterEditionArgs {
+    /// Mint of the master edition
+    pub mint: Pubkey,
+    /// Token account of the pass book authority holding the master edition token
+    pub token_account: Pubkey,
+}
+
+/// Refunds paid by the authority when force deleting a pass book
+#[derive(Debug)]
+pub struct PassBookRefundArgs {
+    /// Authority token account paying the refunds, the authority wallet for native SOL
+    pub source_token_account: Pubkey,
+    /// SPL token program, the system program for native SOL
+    pub token_program: Pubkey,
+    /// Members to refund
+    pub members: Vec<MemberRefundArgs>,
+}
+
+/// Member refunded when force deleting a pass book
+#[derive(Debug)]
+pub struct MemberRefundArgs {
+    /// Membership bought from the pass book
+    pub membership: Pubkey,
+    /// Token account receiving the refund, the member wallet for native SOL
+    pub token_account: Pubkey,
 }
 
 /// Pack set
@@ -116,6 +212,32 @@ pub struct PassBook {
     pub mint: Pubkey,
     /// market_authority that must sign the transaction to buy or mint
     pub market_authority: Option<Pubkey>,
+    /// creators sharing the sales revenue, paid in order
+    pub creators: Vec<Creator>,
+    /// Maximum number of passes a single wallet can buy
+    pub max_per_wallet: Option<u64>,
+    /// Unix timestamp before which passes can't be bought
+    pub sale_start: Option<u64>,
+    /// Unix timestamp after which passes can't be bought
+    pub sale_end: Option<u64>,
+    /// Merkle root of the wallets allowed to buy, anyone can buy if not set
+    pub allowlist_root: Option<[u8; 32]>,
+    /// Mint of the vaulted master edition, editions are printed on purchase if set
+    pub master_edition_mint: Option<Pubkey>,
+    /// Latest expiry of the memberships sold, u64::MAX if one never expires
+    pub members_expire_at: u64,
+    /// Printed passes are frozen in the buyer wallet
+    pub soulbound: bool,
+    /// Seconds after a purchase during which the member can get a refund, purchases are final if not set
+    pub refund_window: Option<u64>,
+    /// Seconds the revenue of a purchase is held in escrow, paid out on purchase if not set
+    pub escrow_hold_period: Option<u64>,
+    /// Revenue held in escrow, oldest release first
+    pub escrow_buckets: Vec<EscrowBucket>,
+    /// Days a member can pause the membership for, memberships can't be paused if not set
+    pub max_pause_days: Option<u64>,
+    /// Number of memberships referring to this pass book
+    pub member_count: u64,
 }
 
 impl PassBook {
@@ -135,6 +257,40 @@ impl PassBook {
         self.created_at = params.created_at;
         self.price = params.price;
         self.market_authority = params.market_authority;
+        self.creators = params.creators;
+        self.max_per_wallet = params.max_per_wallet;
+        self.sale_start = params.sale_start;
+        self.sale_end = params.sale_end;
+        self.allowlist_root = params.allowlist_root;
+        self.master_edition_mint = params.master_edition_mint;
+        self.members_expire_at = 0;
+        self.soulbound = params.soulbound;
+        self.refund_window = params.refund_window;
+        self.escrow_hold_period = params.escrow_hold_period;
+        self.escrow_buckets = Vec::new();
+        self.max_pause_days = params.max_pause_days;
+        self.member_count = 0;
+    }
+
+    /// Check creators list is not empty, not too long, without duplicates and shares sum to 100
+    pub fn assert_valid_creators(creators: &[Creator]) -> Result<(), ProgramError> {
+        if creators.is_empty() || creators.len() > MAX_CREATOR_LIMIT {
+            return Err(NFTPassError::WrongCreatorsLength.into());
+        }
+
+        let mut total_share: u64 = 0;
+        for (i, creator) in creators.iter().enumerate() {
+            if creators[..i].iter().any(|c| c.address == creator.address) {
+                return Err(NFTPassError::DuplicateCreatorAddress.into());
+            }
+            total_share = total_share.error_add(creator.share as u64)?;
+        }
+
+        if total_share != 100 {
+            return Err(NFTPassError::WrongCreatorsShares.into());
+        }
+
+        Ok(())
     }
 
     /// Increment total passes
@@ -151,6 +307,247 @@ impl PassBook {
         Ok(())
     }
 
+    /// Decrement total passes
+    pub fn decrement_supply(&mut self) -> Result<(), ProgramError> {
+        self.supply = self.supply.error_decrement()?;
+        Ok(())
+    }
+
+    /// Keep track of the latest expiry of the memberships sold, `None` never expires
+    pub fn record_member_expiry(&mut self, expires_at: Option<u64>) {
+        let expires_at = expires_at.unwrap_or(u64::MAX);
+        self.members_expire_at = self.members_expire_at.max(expires_at);
+    }
+
+    /// Count a membership now referring to this pass book
+    pub fn increment_member_count(&mut self) -> Result<(), ProgramError> {
+        self.member_count = self.member_count.error_increment()?;
+        Ok(())
+    }
+
+    /// Stop counting a membership referring to another pass book,
+    /// memberships left by a deleted pass book at the same address were never counted
+    pub fn decrement_member_count(&mut self) {
+        self.member_count = self.member_count.saturating_sub(1);
+    }
+
+    /// Check whether memberships sold from this pass book may still be live
+    pub fn has_live_members(&self, now: u64) -> bool {
+        self.members_expire_at >= now
+    }
+
+    /// Refund for the time left on a membership bought at `price`, the full price without expiry
+    pub fn refund_amount(
+        &self,
+        price: u64,
+        expires_at: Option<u64>,
+        now: u64,
+    ) -> Result<u64, ProgramError> {
+        let (expires_at, access) = match (expires_at, self.access) {
+            (Some(expires_at), Some(access)) => (expires_at, access),
+            _ => return Ok(price),
+        };
+        let period = access
+            .checked_mul(86400)
+            .ok_or(NFTPassError::MathOverflow)?;
+        if period == 0 {
+            return Ok(0);
+        }
+        let remaining = expires_at.saturating_sub(now) as u128;
+        let refund = (price as u128)
+            .checked_mul(remaining)
+            .ok_or(NFTPassError::MathOverflow)?
+            / period as u128;
+        u64::try_from(refund).map_err(|_| NFTPassError::MathOverflow.into())
+    }
+
+    /// Check the refund window ends before the revenue leaves the escrow
+    pub fn assert_valid_refund_window(
+        refund_window: Option<u64>,
+        escrow_hold_period: Option<u64>,
+    ) -> Result<(), ProgramError> {
+        if let (Some(refund_window), Some(escrow_hold_period)) = (refund_window, escrow_hold_period)
+        {
+            if refund_window > escrow_hold_period {
+                return Err(NFTPassError::RefundWindowExceedsHoldPeriod.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Release time of the revenue of a purchase, rounded up so the hold period spans
+    /// at most `MAX_ESCROW_BUCKETS - 1` release times
+    pub fn escrow_release_at(&self, purchased_at: u64) -> Result<u64, ProgramError> {
+        let escrow_hold_period = self
+            .escrow_hold_period
+            .ok_or(NFTPassError::EscrowNotEnabled)?;
+        let bucket_span = escrow_hold_period
+            .error_add(MAX_ESCROW_BUCKETS as u64 - 2)?
+            .error_div(MAX_ESCROW_BUCKETS as u64 - 1)?
+            .max(1);
+        purchased_at
+            .error_add(escrow_hold_period)?
+            .error_add(bucket_span - 1)?
+            .error_div(bucket_span)?
+            .error_mul(bucket_span)
+    }
+
+    /// Hold the revenue of a purchase in escrow until its own release time, earlier
+    /// revenue is not held back. A full escrow adds it to the latest bucket instead
+    pub fn deposit_escrow(
+        &mut self,
+        amount: u64,
+        referral_active: bool,
+        now: u64,
+    ) -> Result<(), ProgramError> {
+        let release_at = self.escrow_release_at(now)?;
+        let escrow_full = self.escrow_buckets.len() >= MAX_ESCROW_BUCKETS;
+        let reuse_last = matches!(
+            self.escrow_buckets.last(),
+            Some(last) if last.release_at == release_at || escrow_full
+        );
+        if !reuse_last {
+            self.escrow_buckets.push(EscrowBucket {
+                release_at,
+                ..EscrowBucket::default()
+            });
+        }
+        if let Some(bucket) = self.escrow_buckets.last_mut() {
+            bucket.release_at = bucket.release_at.max(release_at);
+            if referral_active {
+                bucket.referral_amount = bucket.referral_amount.error_add(amount)?;
+            } else {
+                bucket.amount = bucket.amount.error_add(amount)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Take back the revenue of a refunded purchase from the bucket holding it
+    pub fn withdraw_escrow(
+        &mut self,
+        amount: u64,
+        referral_active: bool,
+        purchased_at: u64,
+    ) -> Result<(), ProgramError> {
+        let release_at = self.escrow_release_at(purchased_at)?;
+        let index = self
+            .escrow_buckets
+            .iter()
+            .position(|bucket| bucket.release_at >= release_at)
+            .ok_or(NFTPassError::EscrowEmpty)?;
+        let bucket = &mut self.escrow_buckets[index];
+        if referral_active {
+            bucket.referral_amount = bucket.referral_amount.error_sub(amount)?;
+        } else {
+            bucket.amount = bucket.amount.error_sub(amount)?;
+        }
+        if bucket.amount == 0 && bucket.referral_amount == 0 {
+            self.escrow_buckets.remove(index);
+        }
+        Ok(())
+    }
+
+    /// Remove the buckets released at the given time from the escrow, returns the revenue
+    /// they held without and with an active referral
+    pub fn take_released_escrow(&mut self, now: u64) -> Result<(u64, u64), ProgramError> {
+        let mut amount: u64 = 0;
+        let mut referral_amount: u64 = 0;
+        for bucket in self.escrow_buckets.iter() {
+            if bucket.release_at <= now {
+                amount = amount.error_add(bucket.amount)?;
+                referral_amount = referral_amount.error_add(bucket.referral_amount)?;
+            }
+        }
+        self.escrow_buckets.retain(|bucket| bucket.release_at > now);
+        Ok((amount, referral_amount))
+    }
+
+    /// Longest time a membership can be paused for in each period, in seconds
+    pub fn max_pause_duration(&self) -> Result<u64, ProgramError> {
+        let max_pause_days = self
+            .max_pause_days
+            .ok_or(NFTPassError::PauseNotAllowed)?;
+        max_pause_days.error_mul(86400)
+    }
+
+    /// Check whether revenue is still held in escrow
+    pub fn has_escrow_balance(&self) -> bool {
+        self.escrow_buckets
+            .iter()
+            .any(|bucket| bucket.amount > 0 || bucket.referral_amount > 0)
+    }
+
+    /// Check the wallet did not already buy the maximum number of passes
+    pub fn assert_wallet_can_buy(&self, already_bought: u64) -> Result<(), ProgramError> {
+        if let Some(max_per_wallet) = self.max_per_wallet {
+            if already_bought >= max_per_wallet {
+                return Err(NFTPassError::UserReachBuyLimit.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the sale window opens before it closes
+    pub fn assert_valid_sale_window(
+        sale_start: Option<u64>,
+        sale_end: Option<u64>,
+    ) -> Result<(), ProgramError> {
+        if let (Some(sale_start), Some(sale_end)) = (sale_start, sale_end) {
+            if sale_start >= sale_end {
+                return Err(NFTPassError::WrongSaleWindow.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the pass book is on sale at the given time
+    pub fn assert_on_sale(&self, now: u64) -> Result<(), ProgramError> {
+        if let Some(sale_start) = self.sale_start {
+            if now < sale_start {
+                return Err(NFTPassError::SaleNotStarted.into());
+            }
+        }
+
+        if let Some(sale_end) = self.sale_end {
+            if now > sale_end {
+                return Err(NFTPassError::SaleEnded.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the wallet is in the allowlist and did not buy more than its allocation
+    pub fn assert_allowlisted(
+        &self,
+        wallet: &Pubkey,
+        allowlist_proof: Option<&AllowlistProof>,
+        already_bought: u64,
+    ) -> Result<(), ProgramError> {
+        let root = match &self.allowlist_root {
+            Some(root) => root,
+            None => return Ok(()),
+        };
+
+        let allowlist_proof = allowlist_proof.ok_or(NFTPassError::NotInAllowlist)?;
+        let leaf = allowlist_leaf(wallet, allowlist_proof.allocation);
+        if !verify_proof(root, leaf, &allowlist_proof.proof) {
+            return Err(NFTPassError::NotInAllowlist.into());
+        }
+
+        if let Some(allocation) = allowlist_proof.allocation {
+            if already_bought >= allocation {
+                return Err(NFTPassError::UserReachBuyLimit.into());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check if pass is in activated state
     pub fn assert_activated(&self) -> Result<(), ProgramError> {
         if self.state != PassBookState::Activated {
@@ -1,6 +1,7 @@
 //! Pass definitions
 
 use super::*;
+use crate::{error::NFTPassError, math::SafeMath};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     borsh::try_from_slice_unchecked,
@@ -56,6 +57,29 @@ impl Payout {
         self.mint = mint;
         self.treasury_holder = treasury_holder;
     }
+
+    /// Balance that can still be withdrawn
+    pub fn available_balance(&self) -> Result<u64, ProgramError> {
+        self.cash_in.error_sub(self.cash_out)
+    }
+
+    /// Record a withdrawal, rejecting amounts above the available balance
+    pub fn withdraw(&mut self, amount: u64) -> Result<(), ProgramError> {
+        if amount == 0 || amount > self.available_balance()? {
+            return Err(NFTPassError::InsufficientPayoutBalance.into());
+        }
+        self.cash_out = self.cash_out.error_add(amount)?;
+        Ok(())
+    }
+
+    /// Take back an amount paid in, rejecting amounts above the available balance
+    pub fn refund(&mut self, amount: u64) -> Result<(), ProgramError> {
+        if amount > self.available_balance()? {
+            return Err(NFTPassError::InsufficientPayoutBalance.into());
+        }
+        self.cash_in = self.cash_in.error_sub(amount)?;
+        Ok(())
+    }
 }
 
 impl IsInitialized for Payout {
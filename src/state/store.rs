@@ -1,7 +1,7 @@
 //! Pass definitions
 
 use super::*;
-use crate::math::SafeMath;
+use crate::{error::NFTPassError, math::SafeMath};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     borsh::try_from_slice_unchecked,
@@ -19,7 +19,9 @@ pub const MAX_STORE_LEN: usize = 1+
 + 8 // active membership
 + 8 // total pass books
 + 33 // store referrer
-+ 9; // referral end
++ 9 // referral end
++ 8 // expiry bounty
++ 33; // collection mint
 
 /// Pass Store
 #[repr(C)]
@@ -43,6 +45,10 @@ pub struct Store {
     pub referrer: Option<Pubkey>,
     /// Date referral rewards end
     pub referral_end_date: Option<u64>,
+    /// lamports paid from the store balance to whoever expires a membership
+    pub expiry_bounty: u64,
+    /// collection every printed pass of the store is verified in
+    pub collection_mint: Option<Pubkey>,
 }
 
 impl Store {
@@ -56,6 +62,8 @@ impl Store {
         self.active_membership_count = 0;
         self.pass_count = 0;
         self.pass_book_count = 0;
+        self.expiry_bounty = 0;
+        self.collection_mint = None;
     }
 
     /// Increment the total editions redeemed
@@ -70,23 +78,55 @@ impl Store {
         Ok(())
     }
 
+    /// Decrement the total number of memberships
+    pub fn decrement_membership_count(&mut self) -> Result<(), ProgramError> {
+        self.membership_count = self.membership_count.error_decrement()?;
+        Ok(())
+    }
+
     /// Increment the total number of passes issued
     pub fn increment_pass_count(&mut self) -> Result<(), ProgramError> {
         self.pass_count = self.pass_count.error_increment()?;
         Ok(())
     }
 
+    /// Check the referrer earns a share of a purchase made at the given time
+    pub fn is_referral_active(&self, at: u64) -> bool {
+        match self.referral_end_date {
+            Some(referral_end_date) => at <= referral_end_date,
+            None => true,
+        }
+    }
+
+    /// Decrement the total number of passes issued
+    pub fn decrement_pass_count(&mut self) -> Result<(), ProgramError> {
+        self.pass_count = self.pass_count.error_decrement()?;
+        Ok(())
+    }
+
     /// Increment the total number of active membership issued
     pub fn increment_active_membership_count(&mut self) -> Result<(), ProgramError> {
         self.active_membership_count = self.active_membership_count.error_increment()?;
         Ok(())
     }
 
+    /// Decrement the total number of active membership
+    pub fn decrement_active_membership_count(&mut self) -> Result<(), ProgramError> {
+        self.active_membership_count = self.active_membership_count.error_decrement()?;
+        Ok(())
+    }
+
     /// Increment the total number of master edition passes
     pub fn increment_pass_book_count(&mut self) -> Result<(), ProgramError> {
         self.pass_book_count = self.pass_book_count.error_increment()?;
         Ok(())
     }
+
+    /// Decrement the total number of master edition passes
+    pub fn decrement_pass_book_count(&mut self) -> Result<(), ProgramError> {
+        self.pass_book_count = self.pass_book_count.error_decrement()?;
+        Ok(())
+    }
 }
 
 impl IsInitialized for Store {
@@ -136,6 +176,21 @@ impl StoreAuthority {
         self.store = store;
         self.bump = bump;
     }
+
+    /// Add redemptions to the allowed quota
+    pub fn add_allowed_redemptions(&mut self, count: u64) -> Result<(), ProgramError> {
+        self.allowed_redemptions = self.allowed_redemptions.error_add(count)?;
+        Ok(())
+    }
+
+    /// Consume redemptions from the allowed quota
+    pub fn consume_allowed_redemptions(&mut self, count: u64) -> Result<(), ProgramError> {
+        if count > self.allowed_redemptions {
+            return Err(NFTPassError::NotEnoughAllowedRedemptions.into());
+        }
+        self.allowed_redemptions = self.allowed_redemptions.error_sub(count)?;
+        Ok(())
+    }
 }
 
 impl IsInitialized for StoreAuthority {
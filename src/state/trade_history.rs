@@ -45,6 +45,12 @@ impl TradeHistory {
         self.already_bought = self.already_bought.error_increment()?;
         Ok(())
     }
+
+    /// Decrement the already bought
+    pub fn decrement_already_bought(&mut self) -> Result<(), ProgramError> {
+        self.already_bought = self.already_bought.error_decrement()?;
+        Ok(())
+    }
 }
 
 impl IsInitialized for TradeHistory {
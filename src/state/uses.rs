@@ -8,6 +8,7 @@ use solana_program::{
 };
 
 use super::{AccountType, USE_AUTHORITY_LENGTH};
+use crate::{error::NFTPassError, math::SafeMath};
 
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
@@ -34,6 +35,15 @@ impl UseAuthority {
         self.membership = membership;
         self.bump = bump;
     }
+
+    /// Consume allowed uses
+    pub fn consume_allowed_uses(&mut self, count: u64) -> Result<(), ProgramError> {
+        if count > self.allowed_uses {
+            return Err(NFTPassError::NotEnoughAllowedUses.into());
+        }
+        self.allowed_uses = self.allowed_uses.error_sub(count)?;
+        Ok(())
+    }
 }
 
 impl IsInitialized for UseAuthority {
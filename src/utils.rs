@@ -142,6 +142,43 @@ pub fn spl_token_transfer<'a>(
     invoke_signed(&ix, &[source, destination, authority], signers_seeds)
 }
 
+/// SPL approve instruction.
+pub fn spl_token_approve<'a>(
+    source: AccountInfo<'a>,
+    delegate: AccountInfo<'a>,
+    owner: AccountInfo<'a>,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let ix = spl_token::instruction::approve(
+        &spl_token::id(),
+        source.key,
+        delegate.key,
+        owner.key,
+        &[],
+        amount,
+    )?;
+
+    invoke(&ix, &[source, delegate, owner])
+}
+
+/// Close token account
+pub fn spl_token_close_account<'a>(
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    let ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        account.key,
+        destination.key,
+        authority.key,
+        &[],
+    )?;
+
+    invoke_signed(&ix, &[account, destination, authority], signers_seeds)
+}
+
 /// Native instruction.
 pub fn native_transfer<'a>(
     source: AccountInfo<'a>,
@@ -155,6 +192,20 @@ pub fn native_transfer<'a>(
     )
 }
 
+/// Native transfer from a program address.
+pub fn native_transfer_signed<'a>(
+    source: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    amount: u64,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    invoke_signed(
+        &system_instruction::transfer(source.key, destination.key, amount),
+        &[source, destination],
+        signers_seeds,
+    )
+}
+
 /// SPL transfer instruction.
 pub fn sign_metadata<'a>(
     authority: &AccountInfo<'a>,
@@ -358,7 +409,7 @@ pub fn mpl_mint_new_edition_from_master_edition_via_token<'a>(
     new_mint: &AccountInfo<'a>,
     new_mint_authority: &AccountInfo<'a>,
     payer: &AccountInfo<'a>,
-    user_wallet: &AccountInfo<'a>,
+    update_authority: &AccountInfo<'a>,
     token_account_owner: &AccountInfo<'a>,
     token_account: &AccountInfo<'a>,
     master_metadata: &AccountInfo<'a>,
@@ -381,7 +432,7 @@ pub fn mpl_mint_new_edition_from_master_edition_via_token<'a>(
         *payer.key,
         *token_account_owner.key,
         *token_account.key,
-        *user_wallet.key,
+        *update_authority.key,
         *master_metadata.key,
         *metadata_mint,
         edition,
@@ -396,10 +447,10 @@ pub fn mpl_mint_new_edition_from_master_edition_via_token<'a>(
             new_mint.clone(),
             edition_marker.clone(),
             new_mint_authority.clone(),
-            user_wallet.clone(),
+            payer.clone(),
             token_account_owner.clone(),
             token_account.clone(),
-            user_wallet.clone(),
+            update_authority.clone(),
             master_metadata.clone(),
             token_program.clone(),
             system_program.clone(),
@@ -409,6 +460,159 @@ pub fn mpl_mint_new_edition_from_master_edition_via_token<'a>(
     )
 }
 
+/// Wrapper of `freeze_delegated_account` instruction from `mpl_token_metadata` program
+#[inline(always)]
+pub fn mpl_freeze_delegated_account<'a>(
+    delegate: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    edition: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    signers_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let tx = mpl_token_metadata::instruction::freeze_delegated_account(
+        mpl_token_metadata::id(),
+        *delegate.key,
+        *token_account.key,
+        *edition.key,
+        *mint.key,
+    );
+
+    invoke_signed(
+        &tx,
+        &[
+            delegate.clone(),
+            token_account.clone(),
+            edition.clone(),
+            mint.clone(),
+            token_program.clone(),
+        ],
+        &[signers_seeds],
+    )
+}
+
+/// Wrapper of `thaw_delegated_account` instruction from `mpl_token_metadata` program
+#[inline(always)]
+pub fn mpl_thaw_delegated_account<'a>(
+    delegate: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    edition: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    signers_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let tx = mpl_token_metadata::instruction::thaw_delegated_account(
+        mpl_token_metadata::id(),
+        *delegate.key,
+        *token_account.key,
+        *edition.key,
+        *mint.key,
+    );
+
+    invoke_signed(
+        &tx,
+        &[
+            delegate.clone(),
+            token_account.clone(),
+            edition.clone(),
+            mint.clone(),
+            token_program.clone(),
+        ],
+        &[signers_seeds],
+    )
+}
+
+/// Wrapper of `set_and_verify_collection` instruction from `mpl_token_metadata` program
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn mpl_set_and_verify_collection<'a>(
+    metadata: &AccountInfo<'a>,
+    collection_authority: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    update_authority: &AccountInfo<'a>,
+    collection_mint: &AccountInfo<'a>,
+    collection_metadata: &AccountInfo<'a>,
+    collection_edition: &AccountInfo<'a>,
+    signers_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let tx = mpl_token_metadata::instruction::set_and_verify_collection(
+        mpl_token_metadata::id(),
+        *metadata.key,
+        *collection_authority.key,
+        *payer.key,
+        *update_authority.key,
+        *collection_mint.key,
+        *collection_metadata.key,
+        *collection_edition.key,
+        None,
+    );
+
+    invoke_signed(
+        &tx,
+        &[
+            metadata.clone(),
+            collection_authority.clone(),
+            payer.clone(),
+            update_authority.clone(),
+            collection_mint.clone(),
+            collection_metadata.clone(),
+            collection_edition.clone(),
+        ],
+        &[signers_seeds],
+    )
+}
+
+/// Wrapper of `update_metadata_accounts_v2` instruction from `mpl_token_metadata` program
+#[inline(always)]
+pub fn mpl_update_metadata_accounts_v2<'a>(
+    metadata: &AccountInfo<'a>,
+    update_authority: &AccountInfo<'a>,
+    data: mpl_token_metadata::state::DataV2,
+    signers_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let tx = mpl_token_metadata::instruction::update_metadata_accounts_v2(
+        mpl_token_metadata::id(),
+        *metadata.key,
+        *update_authority.key,
+        None,
+        Some(data),
+        None,
+        None,
+    );
+
+    invoke_signed(
+        &tx,
+        &[metadata.clone(), update_authority.clone()],
+        &[signers_seeds],
+    )
+}
+
+/// Wrapper of `update_metadata_accounts_v2` instruction from `mpl_token_metadata` program
+/// handing the update authority over
+#[inline(always)]
+pub fn mpl_set_update_authority<'a>(
+    metadata: &AccountInfo<'a>,
+    update_authority: &AccountInfo<'a>,
+    new_update_authority: Pubkey,
+    signers_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let tx = mpl_token_metadata::instruction::update_metadata_accounts_v2(
+        mpl_token_metadata::id(),
+        *metadata.key,
+        *update_authority.key,
+        Some(new_update_authority),
+        None,
+        None,
+        None,
+    );
+
+    invoke_signed(
+        &tx,
+        &[metadata.clone(), update_authority.clone()],
+        &[signers_seeds],
+    )
+}
+
 /// Wrapper of `update_primary_sale_happened_via_token` instruction from `mpl_token_metadata` program
 #[inline(always)]
 pub fn mpl_update_primary_sale_happened_via_token<'a>(
@@ -0,0 +1,91 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, state::AccountType};
+use num_traits::FromPrimitive;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, test_store, _, creator, _) = setup_membership(Some(3)).await;
+    let staff = Keypair::new();
+    let store_authority = TestStoreAuthority::new(&test_store.pubkey, &staff.pubkey());
+
+    test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+            5,
+        )
+        .await
+        .unwrap();
+
+    let store_authority_data = store_authority.get_data(&mut context).await;
+    assert_eq!(store_authority_data.account_type, AccountType::StoreAuthority);
+    assert_eq!(store_authority_data.store, test_store.pubkey);
+    assert_eq!(store_authority_data.allowed_redemptions, 5);
+}
+
+#[tokio::test]
+async fn failure_already_exists() {
+    let (mut context, _, test_store, _, creator, _) = setup_membership(Some(3)).await;
+    let staff = Keypair::new();
+    let store_authority = TestStoreAuthority::new(&test_store.pubkey, &staff.pubkey());
+
+    test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+            5,
+        )
+        .await
+        .unwrap();
+
+    let result = test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+            6,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::StoreAuthorityAlreadyExists,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_invalid_store_authority() {
+    let (mut context, _, test_store, _, _, _) = setup_membership(Some(3)).await;
+    let staff = Keypair::new();
+    let store_authority = TestStoreAuthority::new(&test_store.pubkey, &staff.pubkey());
+
+    let result = test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &Keypair::new(),
+            &staff.pubkey(),
+            5,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidStoreKey,
+        0
+    );
+}
@@ -0,0 +1,57 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, state::AccountType};
+use num_traits::FromPrimitive;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, _, membership, _, buyer) = setup_membership(Some(3)).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+
+    membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            2,
+        )
+        .await
+        .unwrap();
+
+    let use_authority_data = use_authority.get_data(&mut context).await;
+    assert_eq!(use_authority_data.account_type, AccountType::UseAuthority);
+    assert_eq!(use_authority_data.membership, membership.pubkey);
+    assert_eq!(use_authority_data.allowed_uses, 2);
+}
+
+#[tokio::test]
+async fn failure_invalid_owner() {
+    let (mut context, _, _, membership, _, _) = setup_membership(Some(3)).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+
+    let result = membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &Keypair::new(),
+            &delegate.pubkey(),
+            2,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidMembershipAuthority,
+        0
+    );
+}
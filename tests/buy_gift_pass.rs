@@ -0,0 +1,211 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, instruction, state::MembershipState};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::InstructionError, program_pack::Pack};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+async fn setup(
+    amount: u64,
+    max_per_wallet: Option<u64>,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestTradeHistory,
+    TestSplToken,
+    User,
+) {
+    let (user, _, _, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, _) =
+        set_up_pass_book_data(&user, &buyer, amount, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    (context, test_pass, test_store, trade_history, token, buyer)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, test_store, trade_history, token, buyer) =
+        setup(10_000_000, None).await;
+    let beneficiary = Keypair::new();
+    let membership = TestMembership::new(&test_store.pubkey, &beneficiary.pubkey());
+
+    test_pass
+        .gift(
+            &mut context,
+            &test_store,
+            &buyer,
+            &beneficiary.pubkey(),
+            &membership,
+            None,
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let membership = membership.get_data(&mut context).await;
+    assert_eq!(membership.owner, beneficiary.pubkey());
+    assert_eq!(membership.state, MembershipState::Activated);
+    assert_eq!(membership.passbook, Some(test_pass.pubkey));
+
+    // the payer keeps the trade history and paid the price
+    let trade_history = trade_history.get_data(&mut context).await;
+    assert_eq!(trade_history.wallet, buyer.pubkey());
+    assert_eq!(trade_history.already_bought, 1);
+
+    let buyer_token_account = get_account(&mut context, &buyer.token_account.pubkey()).await;
+    let buyer_token = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+    assert_eq!(buyer_token.amount, 0);
+
+    let buyer_membership = TestMembership::new(&test_store.pubkey, &buyer.pubkey());
+    assert!(is_empty_account(&mut context, &buyer_membership.pubkey).await);
+}
+
+#[tokio::test]
+async fn failure_beneficiary_has_active_membership() {
+    let (mut context, test_pass, test_store, trade_history, token, buyer) =
+        setup(20_000_000, None).await;
+    let beneficiary = Keypair::new();
+    let membership = TestMembership::new(&test_store.pubkey, &beneficiary.pubkey());
+
+    test_pass
+        .gift(
+            &mut context,
+            &test_store,
+            &buyer,
+            &beneficiary.pubkey(),
+            &membership,
+            None,
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    let result = test_pass
+        .gift(
+            &mut context,
+            &test_store,
+            &buyer,
+            &beneficiary.pubkey(),
+            &membership,
+            None,
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::UserHasActiveMembership,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_payer_reach_buy_limit() {
+    let (mut context, test_pass, test_store, trade_history, token, buyer) =
+        setup(20_000_000, Some(1)).await;
+    let beneficiary = Keypair::new();
+    let membership = TestMembership::new(&test_store.pubkey, &beneficiary.pubkey());
+
+    test_pass
+        .gift(
+            &mut context,
+            &test_store,
+            &buyer,
+            &beneficiary.pubkey(),
+            &membership,
+            None,
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // the gift counts towards the limit of the paying wallet, not of the beneficiary
+    let other_beneficiary = Keypair::new();
+    let other_membership = TestMembership::new(&test_store.pubkey, &other_beneficiary.pubkey());
+
+    let result = test_pass
+        .gift(
+            &mut context,
+            &test_store,
+            &buyer,
+            &other_beneficiary.pubkey(),
+            &other_membership,
+            None,
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::UserReachBuyLimit,
+        0
+    );
+}
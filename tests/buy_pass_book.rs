@@ -1,6 +1,11 @@
 mod utils;
 
-use nft_pass_book::{error::NFTPassError, instruction};
+use nft_pass_book::{
+    error::NFTPassError,
+    find_pass_program_address, find_program_authority, instruction,
+    merkle::{allowlist_leaf, AllowlistTree},
+    state::{Creator, Pass, PassMintArgs, PassState},
+};
 use num_traits::FromPrimitive;
 use solana_program::{clock::Clock, instruction::InstructionError, program_pack::Pack};
 use solana_program_test::*;
@@ -102,12 +107,27 @@ async fn success_buy_with_spl_token() {
                 price: 10_000_000,
                 has_referrer: referrer.is_some(), // Some(referrer.pubkey()),
                 has_market_authority: market_place_user.is_some(),
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await
         .unwrap();
 
+    TestMarketConfig::new(&market_place_user.unwrap().pubkey())
+        .set(&mut context, market_place_user.unwrap(), 250, 50, 0)
+        .await
+        .unwrap();
+
     test_pass
         .buy(
             &mut context,
@@ -117,9 +137,9 @@ async fn success_buy_with_spl_token() {
             market_place_user,
             &trade_history,
             instruction::BuyPassArgs {
-                market_fee_basis_point: 250,
-                referral_share: 50,
-                referral_kick_back_share: 0,
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
             },
         )
         .await
@@ -210,7 +230,17 @@ async fn success_buy_with_native_token() {
                 price: 10_000_000,
                 has_referrer: referrer.is_some(), // Some(referrer.pubkey()),
                 has_market_authority: market_place_user.is_some(),
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await
@@ -232,6 +262,11 @@ async fn success_buy_with_native_token() {
         get_account(&mut context, &referrer_payout.treasury_holder).await;
     let referrer_initial_lamport = referrer_token_holder_account.lamports;
 
+    TestMarketConfig::new(&market_place_user.unwrap().pubkey())
+        .set(&mut context, market_place_user.unwrap(), 250, 50, 0)
+        .await
+        .unwrap();
+
     test_pass
         .buy(
             &mut context,
@@ -241,9 +276,9 @@ async fn success_buy_with_native_token() {
             market_place_user,
             &trade_history,
             instruction::BuyPassArgs {
-                market_fee_basis_point: 250,
-                referral_share: 50,
-                referral_kick_back_share: 0,
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
             },
         )
         .await
@@ -341,7 +376,17 @@ async fn failure_multiple_buy() {
                 price: 10_000_000,
                 has_referrer: referrer.is_some(), // Some(referrer.pubkey()),
                 has_market_authority: market_place_user.is_some(),
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await
@@ -350,6 +395,11 @@ async fn failure_multiple_buy() {
     let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
     context.warp_to_slot(clock.slot + 1500).unwrap();
 
+    TestMarketConfig::new(&market_place_user.unwrap().pubkey())
+        .set(&mut context, market_place_user.unwrap(), 250, 50, 0)
+        .await
+        .unwrap();
+
     test_pass
         .buy(
             &mut context,
@@ -359,9 +409,9 @@ async fn failure_multiple_buy() {
             market_place_user,
             &trade_history,
             instruction::BuyPassArgs {
-                market_fee_basis_point: 250,
-                referral_share: 50,
-                referral_kick_back_share: 0,
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
             },
         )
         .await
@@ -378,9 +428,9 @@ async fn failure_multiple_buy() {
             market_place_user,
             &trade_history,
             instruction::BuyPassArgs {
-                market_fee_basis_point: 250,
-                referral_share: 50,
-                referral_kick_back_share: 0,
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
             },
         )
         .await;
@@ -391,3 +441,677 @@ async fn failure_multiple_buy() {
         0
     );
 }
+
+#[tokio::test]
+async fn success_buy_with_multiple_creators() {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+    let second_creator = Keypair::new();
+    let third_creator = Keypair::new();
+
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        setup(&user, &buyer, 10_000_001, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_001,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: Some(vec![
+                    Creator {
+                        address: user.pubkey(),
+                        share: 33,
+                    },
+                    Creator {
+                        address: second_creator.pubkey(),
+                        share: 33,
+                    },
+                    Creator {
+                        address: third_creator.pubkey(),
+                        share: 34,
+                    },
+                ]),
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_001,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // 9_750_001 for creators, the remainder dust goes to the first creator
+    let first_payout = TestPayout::new(&user.pubkey(), &token.pubkey())
+        .get_data(&mut context)
+        .await;
+    assert_eq!(first_payout.cash_in, 3_217_501);
+
+    let second_payout = TestPayout::new(&second_creator.pubkey(), &token.pubkey())
+        .get_data(&mut context)
+        .await;
+    assert_eq!(second_payout.cash_in, 3_217_500);
+
+    let third_payout = TestPayout::new(&third_creator.pubkey(), &token.pubkey())
+        .get_data(&mut context)
+        .await;
+    assert_eq!(third_payout.cash_in, 3_315_000);
+}
+
+#[tokio::test]
+async fn failure_price_mismatch() {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        setup(&user, &buyer, 20_000_000, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    // the creator raised the price after the buyer saw it
+    test_pass
+        .edit(
+            &mut context,
+            &user,
+            None,
+            None,
+            None,
+            None,
+            Some(20_000_000),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let result = test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::PriceMismatch,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_mint_mismatch() {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        setup(&user, &buyer, 20_000_000, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    let result = test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: spl_token::native_mint::id(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MintMismatch,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_wallet_limit_reached() {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        setup(&user, &buyer, 20_000_000, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: Some(1),
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // the wallet limit is checked before the active membership
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + 1500).unwrap();
+
+    let result = test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::UserReachBuyLimit,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_outside_sale_window() {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        setup(&user, &buyer, 20_000_000, false).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: Some(clock.unix_timestamp as u64 + 86400),
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    let result = test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::SaleNotStarted,
+        0
+    );
+
+    test_pass
+        .edit_with_args(
+            &mut context,
+            &user,
+            None,
+            instruction::EditPassBookArgs {
+                sale_start: Some(None),
+                sale_end: Some(Some(clock.unix_timestamp as u64 - 1)),
+                ..instruction::EditPassBookArgs::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let result = test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::SaleEnded,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_buy_with_allowlist() {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        setup(&user, &buyer, 20_000_000, false).await;
+
+    let tree = AllowlistTree::new(vec![
+        allowlist_leaf(&Keypair::new().pubkey(), None),
+        allowlist_leaf(&buyer.pubkey(), Some(1)),
+        allowlist_leaf(&Keypair::new().pubkey(), Some(3)),
+    ]);
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: tree.root(),
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    let result = test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::NotInAllowlist,
+        0
+    );
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: Some(instruction::AllowlistProof {
+                    allocation: Some(1),
+                    proof: tree.proof(1),
+                }),
+            },
+        )
+        .await
+        .unwrap();
+
+    let trade_history_data = trade_history.get_data(&mut context).await;
+    assert_eq!(trade_history_data.already_bought, 1);
+}
+
+#[tokio::test]
+async fn success_buy_with_pass_edition() {
+    let (user, _, _, buyer) = setup_users();
+
+    let (mut context, _, test_store, _, token, membership) =
+        setup(&user, &buyer, 20_000_000, false).await;
+
+    let test_metadata = TestMetadata::new();
+    test_metadata
+        .create(
+            &mut context,
+            String::from("Pass"),
+            String::from("PASS"),
+            String::from("some link to storage"),
+            None,
+            10,
+            false,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    TestMasterEditionV2::new(&test_metadata)
+        .create(&mut context, None)
+        .await
+        .unwrap();
+    let (program_authority, _) = find_program_authority(&nft_pass_book::id());
+    test_metadata
+        .set_update_authority(&mut context, &program_authority)
+        .await
+        .unwrap();
+
+    let test_pass = TestPassBook::new_with_master_edition(&test_metadata);
+    let trade_history = TestTradeHistory::new(&test_pass.pubkey, &buyer.pubkey());
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: true,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let pass_mint = Keypair::new();
+    let pass_token = Keypair::new();
+    create_mint(&mut context, &pass_mint, &buyer.pubkey(), None)
+        .await
+        .unwrap();
+    create_token_account(&mut context, &pass_token, &pass_mint.pubkey(), &buyer.pubkey())
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut context,
+        &pass_mint.pubkey(),
+        &pass_token.pubkey(),
+        1,
+        &buyer.pubkey(),
+        Some(vec![&buyer.owner]),
+    )
+    .await
+    .unwrap();
+
+    test_pass
+        .buy_with_pass(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            None,
+            &trade_history,
+            Some(&PassMintArgs {
+                mint: pass_mint.pubkey(),
+                master_edition_mint: test_metadata.mint.pubkey(),
+                edition: 1,
+                token_account: pass_token.pubkey(),
+                collection_mint: None,
+            }),
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let (pass_key, _) = find_pass_program_address(&nft_pass_book::id(), &pass_mint.pubkey());
+    let pass_account = get_account(&mut context, &pass_key).await;
+    let pass = Pass::unpack_unchecked(&pass_account.data).unwrap();
+    assert_eq!(pass.owner, buyer.pubkey());
+    assert_eq!(pass.pass_book, test_pass.pubkey);
+    assert_eq!(pass.mint, pass_mint.pubkey());
+    assert_eq!(pass.state, PassState::Activated);
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.pass, Some(pass_key));
+}
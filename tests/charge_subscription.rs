@@ -0,0 +1,249 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, instruction, state::MembershipState};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::InstructionError, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+const PRICE: u64 = 10_000_000;
+
+fn subscribe_args(mint: Pubkey, periods: u64) -> instruction::SubscribeArgs {
+    instruction::SubscribeArgs {
+        expected_price: PRICE,
+        expected_mint: mint,
+        periods,
+    }
+}
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestTradeHistory,
+    TestMembership,
+    User,
+    User,
+    User,
+) {
+    let (user, referrer, market, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, PRICE * 2, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(3),
+                access: Some(30),
+                max_supply: Some(5),
+                price: PRICE,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pass.activate(&mut context, &user).await.unwrap();
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: PRICE,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pass
+        .subscribe(
+            &mut context,
+            &test_store,
+            &buyer,
+            subscribe_args(token.pubkey(), 2),
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_pass,
+        test_store,
+        trade_history,
+        membership,
+        user,
+        market,
+        buyer,
+    )
+}
+
+async fn warp_to_expiry(context: &mut ProgramTestContext, membership: &TestMembership) {
+    let expires_at = membership.get_data(context).await.expires_at.unwrap();
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = expires_at as i64;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, test_store, trade_history, membership, user, market, buyer) =
+        setup().await;
+    let before = membership.get_data(&mut context).await;
+    assert_eq!(before.subscription.as_ref().unwrap().periods_left, 2);
+
+    warp_to_expiry(&mut context, &membership).await;
+    test_pass
+        .charge(
+            &mut context,
+            &test_store,
+            &buyer,
+            &trade_history,
+            Some(&market),
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.state, MembershipState::Activated);
+    assert_eq!(
+        membership_data.expires_at.unwrap(),
+        before.expires_at.unwrap() + 30 * 86400
+    );
+    let subscription = membership_data.subscription.unwrap();
+    assert_eq!(subscription.periods_left, 1);
+    assert_eq!(subscription.price, PRICE);
+
+    let passbook = test_pass.get_data(&mut context).await;
+    assert_eq!(passbook.supply, 1);
+    let creator_payout = TestPayout::new(&user.pubkey(), &passbook.mint)
+        .get_data(&mut context)
+        .await;
+    assert_eq!(creator_payout.cash_in, 19_500_000);
+
+    let trade_history_data = trade_history.get_data(&mut context).await;
+    assert_eq!(trade_history_data.already_bought, 2);
+}
+
+#[tokio::test]
+async fn success_lapse_without_funds() {
+    let (mut context, test_pass, test_store, trade_history, membership, _, market, buyer) =
+        setup().await;
+
+    warp_to_expiry(&mut context, &membership).await;
+    test_pass
+        .charge(
+            &mut context,
+            &test_store,
+            &buyer,
+            &trade_history,
+            Some(&market),
+        )
+        .await
+        .unwrap();
+
+    // the member token account is empty after the first charge
+    context.warp_to_slot(3).unwrap();
+    warp_to_expiry(&mut context, &membership).await;
+    test_pass
+        .charge(
+            &mut context,
+            &test_store,
+            &buyer,
+            &trade_history,
+            Some(&market),
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.state, MembershipState::Expired);
+    assert_eq!(membership_data.subscription, None);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.active_membership_count, 0);
+
+    let trade_history_data = trade_history.get_data(&mut context).await;
+    assert_eq!(trade_history_data.already_bought, 2);
+}
+
+#[tokio::test]
+async fn failure_not_due() {
+    let (mut context, test_pass, test_store, trade_history, _, _, market, buyer) = setup().await;
+
+    let result = test_pass
+        .charge(
+            &mut context,
+            &test_store,
+            &buyer,
+            &trade_history,
+            Some(&market),
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::SubscriptionNotDue,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_expire_before_charge() {
+    let (mut context, _, test_store, _, membership, _, _, _) = setup().await;
+
+    let expires_at = membership.get_data(&mut context).await.expires_at.unwrap();
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = expires_at as i64 + 1;
+    context.set_sysvar(&clock);
+
+    let result = membership
+        .expire(&mut context, &test_store.pubkey, &Keypair::new().pubkey())
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipHasSubscription,
+        0
+    );
+
+    let data = membership.get_data(&mut context).await;
+    assert_eq!(data.state, MembershipState::Activated);
+    assert!(data.subscription.is_some());
+}
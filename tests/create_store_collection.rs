@@ -0,0 +1,81 @@
+mod utils;
+
+use mpl_token_metadata::{pda::find_metadata_account, state::Metadata};
+use nft_pass_book::{error::NFTPassError, find_pass_collection_mint};
+use num_traits::FromPrimitive;
+use solana_program::{borsh::try_from_slice_unchecked, instruction::InstructionError};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, user) = setup_pass_book(true).await;
+    let test_store = TestStore::new(&user.pubkey());
+
+    test_store
+        .create_collection(&mut context, &user.owner)
+        .await
+        .unwrap();
+
+    let (collection_mint, _) = find_pass_collection_mint(&nft_pass_book::id(), &test_store.pubkey);
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.collection_mint, Some(collection_mint));
+
+    let mint = get_mint(&mut context, &collection_mint).await;
+    assert_eq!(mint.supply, 1);
+}
+
+#[tokio::test]
+async fn failure_already_created() {
+    let (mut context, _, user) = setup_pass_book(true).await;
+    let test_store = TestStore::new(&user.pubkey());
+
+    test_store
+        .create_collection(&mut context, &user.owner)
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    let result = test_store.create_collection(&mut context, &user.owner).await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::StoreCollectionAlreadyCreated,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_verified_pass() {
+    let (mut context, test_pass, test_store, token, user, buyer, test_metadata) =
+        setup_master_edition_pass_book(false).await;
+
+    test_store
+        .create_collection(&mut context, &user.owner)
+        .await
+        .unwrap();
+    let (collection_mint, _) = find_pass_collection_mint(&nft_pass_book::id(), &test_store.pubkey);
+
+    let (_, pass_mint) = buy_pass_edition(
+        &mut context,
+        &test_pass,
+        &test_store,
+        &token,
+        &buyer,
+        &test_metadata.mint.pubkey(),
+        Some(collection_mint),
+    )
+    .await
+    .unwrap();
+
+    let (pass_metadata, _) = find_metadata_account(&pass_mint.pubkey());
+    let pass_metadata = get_account(&mut context, &pass_metadata).await;
+    let pass_metadata: Metadata = try_from_slice_unchecked(&pass_metadata.data).unwrap();
+    let collection = pass_metadata.collection.unwrap();
+    assert_eq!(collection.key, collection_mint);
+    assert!(collection.verified);
+}
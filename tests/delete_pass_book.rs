@@ -1,7 +1,15 @@
 mod utils;
 
+use nft_pass_book::{
+    error::NFTPassError,
+    find_program_authority, instruction,
+    state::{MemberRefundArgs, MembershipState, PassBookRefundArgs},
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::InstructionError, program_pack::Pack};
 use solana_program_test::*;
-use solana_sdk::{signature::Keypair, signer::Signer};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+use spl_associated_token_account::get_associated_token_address;
 use utils::*;
 
 #[tokio::test]
@@ -18,4 +26,279 @@ async fn success() {
         .unwrap();
 
     assert!(is_empty_account(&mut context, &test_pass.account.pubkey()).await);
-}
\ No newline at end of file
+
+    let store = TestStore::new(&user.pubkey()).get_data(&mut context).await;
+    assert_eq!(store.pass_book_count, 0);
+}
+
+#[tokio::test]
+async fn failure_live_members() {
+    let (mut context, test_pass, _, _, user, _) = setup_membership(None).await;
+
+    let result = test_pass
+        .delete(&mut context, &user, &user.pubkey())
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::PassBookHasLiveMembers,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_force_with_refunds() {
+    let (mut context, test_pass, test_store, membership, user, buyer) =
+        setup_membership(None).await;
+
+    test_pass
+        .delete_with_refunds(
+            &mut context,
+            &user,
+            &user.pubkey(),
+            Some(&PassBookRefundArgs {
+                source_token_account: user.token_account.pubkey(),
+                token_program: spl_token::id(),
+                members: vec![MemberRefundArgs {
+                    membership: membership.pubkey,
+                    token_account: buyer.token_account.pubkey(),
+                }],
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert!(is_empty_account(&mut context, &test_pass.pubkey).await);
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.state, MembershipState::Expired);
+    assert_eq!(membership_data.passbook, None);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.active_membership_count, 0);
+    assert_eq!(store.pass_book_count, 0);
+
+    let buyer_token = get_account(&mut context, &buyer.token_account.pubkey()).await;
+    let buyer_token = spl_token::state::Account::unpack(&buyer_token.data).unwrap();
+    assert!(buyer_token.amount > 0 && buyer_token.amount <= 10_000_000);
+}
+
+#[tokio::test]
+async fn success_force_refund_at_purchase_price() {
+    let (user, referrer, market, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, 10_000_000, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: None,
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: Some(3600),
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pass
+        .edit(&mut context, &user, None, None, None, None, Some(1), None, None, None)
+        .await
+        .unwrap();
+
+    test_pass
+        .delete_with_refunds(
+            &mut context,
+            &user,
+            &user.pubkey(),
+            Some(&PassBookRefundArgs {
+                source_token_account: user.token_account.pubkey(),
+                token_program: spl_token::id(),
+                members: vec![MemberRefundArgs {
+                    membership: membership.pubkey,
+                    token_account: buyer.token_account.pubkey(),
+                }],
+            }),
+        )
+        .await
+        .unwrap();
+
+    // refunded for the time left at the price paid, not the lowered one
+    let buyer_token = get_account(&mut context, &buyer.token_account.pubkey()).await;
+    let buyer_token = spl_token::state::Account::unpack(&buyer_token.data).unwrap();
+    assert!(buyer_token.amount > 9_000_000 && buyer_token.amount <= 10_000_000);
+}
+
+#[tokio::test]
+async fn failure_force_without_members() {
+    let (mut context, test_pass, _, _, user, _) = setup_membership(None).await;
+    assert_eq!(test_pass.get_data(&mut context).await.member_count, 1);
+
+    let result = test_pass
+        .delete_with_refunds(
+            &mut context,
+            &user,
+            &user.pubkey(),
+            Some(&PassBookRefundArgs {
+                source_token_account: user.token_account.pubkey(),
+                token_program: spl_token::id(),
+                members: vec![],
+            }),
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::PassBookHasLiveMembers,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_activated() {
+    let (mut context, test_pass, user) = setup_pass_book(true).await;
+
+    test_pass.activate(&mut context, &user).await.unwrap();
+
+    let result = test_pass
+        .delete(&mut context, &user, &user.pubkey())
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::PassBookIsActivated,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_return_master_edition() {
+    let user = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+    let mut context = nft_pass_book_program_test().start_with_context().await;
+    let test_store = TestStore::new(&user.pubkey());
+
+    let test_metadata = TestMetadata::new();
+    test_metadata
+        .create(
+            &mut context,
+            String::from("Pass"),
+            String::from("PASS"),
+            String::from("some link to storage"),
+            None,
+            10,
+            false,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    TestMasterEditionV2::new(&test_metadata)
+        .create(&mut context, None)
+        .await
+        .unwrap();
+    let (program_authority, _) = find_program_authority(&nft_pass_book::id());
+    test_metadata
+        .set_update_authority(&mut context, &program_authority)
+        .await
+        .unwrap();
+
+    let test_pass = TestPassBook::new_with_master_edition(&test_metadata);
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &spl_token::native_mint::id(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 0,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: true,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pass
+        .delete(&mut context, &user, &user.pubkey())
+        .await
+        .unwrap();
+
+    assert!(is_empty_account(&mut context, &test_pass.pubkey).await);
+
+    let vault = get_associated_token_address(&program_authority, &test_metadata.mint.pubkey());
+    assert!(is_empty_account(&mut context, &vault).await);
+
+    let token_account = get_account(&mut context, &test_metadata.token.pubkey()).await;
+    let token_data = spl_token::state::Account::unpack(&token_account.data).unwrap();
+    assert_eq!(token_data.amount, 1);
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, user.pubkey());
+}
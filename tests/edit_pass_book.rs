@@ -1,11 +1,16 @@
 mod utils;
 
-use nft_pass_book::{error::NFTPassError};
+use nft_pass_book::{
+    error::NFTPassError,
+    instruction,
+    state::{Creator, MAX_NAME_LENGTH},
+};
 use num_traits::FromPrimitive;
 use solana_program::{instruction::InstructionError};
 use solana_program_test::*;
 use solana_sdk::{
-    signature::Keypair, transaction::TransactionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
 };
 use utils::*;
 
@@ -32,6 +37,7 @@ async fn success() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -60,12 +66,36 @@ async fn failure() {
             None,
             None,
             None,
+            None,
         )
         .await;
 
     assert_custom_error!(result.unwrap_err().unwrap(), NFTPassError::CantSetTheSameValue, 0);
 }
 
+#[tokio::test]
+async fn failure_name_too_long() {
+    let (mut context, test_pass, _, _, user, _, _) =
+        setup_master_edition_pass_book(false).await;
+
+    let result = test_pass
+        .edit(
+            &mut context,
+            &user,
+            None,
+            Some("a".repeat(MAX_NAME_LENGTH + 1)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
+
+    assert_custom_error!(result.unwrap_err().unwrap(), NFTPassError::NameTooLong, 0);
+}
+
 #[tokio::test]
 async fn fail_immutable() {
     let (mut context, test_pass, user) = setup_pass_book(false).await;
@@ -81,8 +111,185 @@ async fn fail_immutable() {
             None,
             None,
             None,
+            None,
         )
         .await;
 
     assert_custom_error!(result.unwrap_err().unwrap(), NFTPassError::ImmutablePassBook, 0);
 }
+
+#[tokio::test]
+async fn success_edit_creators() {
+    let (mut context, test_pass, user) = setup_pass_book(true).await;
+    let second_creator = Keypair::new();
+    let creators = vec![
+        Creator {
+            address: user.pubkey(),
+            share: 70,
+        },
+        Creator {
+            address: second_creator.pubkey(),
+            share: 30,
+        },
+    ];
+
+    test_pass
+        .edit(
+            &mut context,
+            &user,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(creators.clone()),
+        )
+        .await
+        .unwrap();
+
+    let pass_book = test_pass.get_data(&mut context).await;
+    assert_eq!(pass_book.creators, creators);
+
+    let second_payout = TestPayout::new(&second_creator.pubkey(), &pass_book.mint)
+        .get_data(&mut context)
+        .await;
+    assert_eq!(second_payout.authority, second_creator.pubkey());
+}
+
+#[tokio::test]
+async fn success_edit_mint() {
+    let (mut context, test_pass, user) = setup_pass_book(true).await;
+    let new_mint = Keypair::new();
+    create_mint(&mut context, &new_mint, &user.pubkey(), None)
+        .await
+        .unwrap();
+
+    test_pass
+        .edit(
+            &mut context,
+            &user,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(&new_mint.pubkey()),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pass_book = test_pass.get_data(&mut context).await;
+    assert_eq!(pass_book.mint, new_mint.pubkey());
+
+    let payout = TestPayout::new(&user.pubkey(), &new_mint.pubkey())
+        .get_data(&mut context)
+        .await;
+    assert_eq!(payout.authority, user.pubkey());
+}
+
+#[tokio::test]
+async fn success_edit_max_per_wallet() {
+    let (mut context, test_pass, user) = setup_pass_book(true).await;
+
+    test_pass
+        .edit_with_args(
+            &mut context,
+            &user,
+            None,
+            instruction::EditPassBookArgs {
+                max_per_wallet: Some(Some(2)),
+                ..instruction::EditPassBookArgs::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let pass_book = test_pass.get_data(&mut context).await;
+    assert_eq!(pass_book.max_per_wallet, Some(2));
+
+    let result = test_pass
+        .edit_with_args(
+            &mut context,
+            &user,
+            None,
+            instruction::EditPassBookArgs {
+                max_per_wallet: Some(Some(0)),
+                ..instruction::EditPassBookArgs::default()
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::WrongMaxPerWallet,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_sync_master_metadata() {
+    let (mut context, test_pass, _, _, user, _, test_metadata) =
+        setup_master_edition_pass_book(false).await;
+
+    test_pass
+        .edit_with_args(
+            &mut context,
+            &user,
+            None,
+            instruction::EditPassBookArgs {
+                name: Some(String::from("New Pass Name")),
+                uri: Some(String::from("new link to storage")),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(
+        metadata.data.name.trim_matches(char::from(0)),
+        String::from("New Pass Name")
+    );
+    assert_eq!(
+        metadata.data.uri.trim_matches(char::from(0)),
+        String::from("new link to storage")
+    );
+    assert_eq!(
+        metadata.data.symbol.trim_matches(char::from(0)),
+        String::from("PASS")
+    );
+}
+
+#[tokio::test]
+async fn success_skip_immutable_metadata() {
+    let (mut context, test_pass, _, _, user, _, test_metadata) =
+        setup_master_edition_pass_book_with_metadata(false, false).await;
+
+    test_pass
+        .edit_with_args(
+            &mut context,
+            &user,
+            None,
+            instruction::EditPassBookArgs {
+                name: Some(String::from("New Pass Name")),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let pass_book = test_pass.get_data(&mut context).await;
+    assert_eq!(
+        pass_book.name.trim_matches(char::from(0)),
+        String::from("New Pass Name")
+    );
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(
+        metadata.data.name.trim_matches(char::from(0)),
+        String::from("Pass")
+    );
+}
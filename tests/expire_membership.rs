@@ -0,0 +1,71 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, state::MembershipState};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::InstructionError, system_instruction};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+#[tokio::test]
+async fn success_with_bounty() {
+    let (mut context, _, test_store, membership, creator, _) = setup_membership(Some(3)).await;
+    let caller = Keypair::new();
+    let bounty = 1_000_000;
+
+    test_store
+        .set_expiry_bounty(&mut context, &creator.owner, bounty)
+        .await
+        .unwrap();
+
+    // fund the store pool
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &test_store.pubkey,
+            bounty * 2,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = membership_data.expires_at.unwrap() as i64 + 1;
+    context.set_sysvar(&clock);
+
+    membership
+        .expire(&mut context, &test_store.pubkey, &caller.pubkey())
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.state, MembershipState::Expired);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.active_membership_count, 0);
+    assert_eq!(store.expiry_bounty, bounty);
+
+    let caller_account = get_account(&mut context, &caller.pubkey()).await;
+    assert_eq!(caller_account.lamports, bounty);
+}
+
+#[tokio::test]
+async fn failure_membership_still_active() {
+    let (mut context, _, test_store, membership, _, _) = setup_membership(Some(3)).await;
+
+    let result = membership
+        .expire(&mut context, &test_store.pubkey, &Keypair::new().pubkey())
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipStillActive,
+        0
+    );
+}
@@ -4,14 +4,16 @@ use nft_pass_book::{
     error::NFTPassError,
     instruction,
     state::{
-        AccountType, PassBookState, MAX_MEMBERSHIP_LEN, MAX_PASS_BOOK_LEN, MAX_PAYOUT_LEN,
+        AccountType, Creator, PassBookState, MAX_MEMBERSHIP_LEN, MAX_PASS_BOOK_LEN, MAX_PAYOUT_LEN,
         MAX_STORE_LEN, MAX_TRADE_HISTORY_LEN, STORE_AUTHORITY_LENGTH, USES_LENGTH,
         USE_AUTHORITY_LENGTH,
     }, // MAX_PASS_BOOK_LEN, MAX_STORE_LEN, MAX&_PAYOUT_LEN},
 };
+use nft_pass_book::find_program_authority;
 use num_traits::FromPrimitive;
-use solana_program::{instruction::InstructionError};
+use solana_program::{instruction::InstructionError, program_pack::Pack};
 use solana_program_test::*;
+use spl_associated_token_account::get_associated_token_address;
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
 use utils::*;
 
@@ -72,7 +74,17 @@ async fn success() {
                 price: 0,
                 has_referrer: true,
                 has_market_authority: true,
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await
@@ -132,7 +144,17 @@ async fn failure() {
                 price: 0,
                 has_referrer: true,
                 has_market_authority: true,
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await;
@@ -192,7 +214,17 @@ async fn success_spl_token() {
                 price: 10_000_000,
                 has_referrer: referrer.is_some(), // Some(referrer.pubkey()),
                 has_market_authority: market_place_user.is_some(),
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await
@@ -211,3 +243,326 @@ async fn success_spl_token() {
     assert_eq!(test_pass.mint, usdc_token.mint.pubkey());
     assert_eq!(test_pass.authority, user.owner.pubkey());
 }
+
+#[tokio::test]
+async fn failure_wrong_creators_shares() {
+    let user = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+    let (mut context, test_pass, test_store) = setup(&user).await;
+
+    let result = test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &spl_token::native_mint::id(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: Some(vec![
+                    Creator {
+                        address: user.pubkey(),
+                        share: 60,
+                    },
+                    Creator {
+                        address: Keypair::new().pubkey(),
+                        share: 30,
+                    },
+                ]),
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::WrongCreatorsShares,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_with_master_edition() {
+    let user = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+    let (mut context, _, test_store) = setup(&user).await;
+
+    let test_metadata = TestMetadata::new();
+    test_metadata
+        .create(
+            &mut context,
+            String::from("Pass"),
+            String::from("PASS"),
+            String::from("some link to storage"),
+            None,
+            10,
+            false,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    let test_master_edition = TestMasterEditionV2::new(&test_metadata);
+    test_master_edition.create(&mut context, None).await.unwrap();
+    let (program_authority, _) = find_program_authority(&nft_pass_book::id());
+    test_metadata
+        .set_update_authority(&mut context, &program_authority)
+        .await
+        .unwrap();
+
+    let test_pass = TestPassBook::new_with_master_edition(&test_metadata);
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &spl_token::native_mint::id(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 0,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: true,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let pass_book = test_pass.get_data(&mut context).await;
+    assert_eq!(
+        pass_book.master_edition_mint,
+        Some(test_metadata.mint.pubkey())
+    );
+
+    let vault = get_associated_token_address(&program_authority, &test_metadata.mint.pubkey());
+    let vault_account = get_account(&mut context, &vault).await;
+    let vault_data = spl_token::state::Account::unpack(&vault_account.data).unwrap();
+    assert_eq!(vault_data.amount, 1);
+    assert_eq!(vault_data.owner, program_authority);
+}
+
+#[tokio::test]
+async fn failure_wrong_master_supply() {
+    let user = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+    let (mut context, _, test_store) = setup(&user).await;
+
+    let test_metadata = TestMetadata::new();
+    test_metadata
+        .create(
+            &mut context,
+            String::from("Pass"),
+            String::from("PASS"),
+            String::from("some link to storage"),
+            None,
+            10,
+            false,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    let test_master_edition = TestMasterEditionV2::new(&test_metadata);
+    test_master_edition
+        .create(&mut context, Some(2))
+        .await
+        .unwrap();
+
+    let test_pass = TestPassBook::new_with_master_edition(&test_metadata);
+    let result = test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &spl_token::native_mint::id(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 0,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: true,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::WrongMasterSupply,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_wrong_update_authority() {
+    let user = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+    let (mut context, _, test_store) = setup(&user).await;
+
+    let test_metadata = TestMetadata::new();
+    test_metadata
+        .create(
+            &mut context,
+            String::from("Pass"),
+            String::from("PASS"),
+            String::from("some link to storage"),
+            None,
+            10,
+            false,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    let test_master_edition = TestMasterEditionV2::new(&test_metadata);
+    test_master_edition.create(&mut context, None).await.unwrap();
+
+    let test_pass = TestPassBook::new_with_master_edition(&test_metadata);
+    let result = test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &spl_token::native_mint::id(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 0,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: true,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidUpdateAuthorityKey,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_soulbound_without_master_edition() {
+    let user = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+    let (mut context, test_pass, test_store) = setup(&user).await;
+
+    let result = test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &spl_token::native_mint::id(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(30),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 0,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: true,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::SoulboundWithoutMasterEdition,
+        0
+    );
+}
@@ -0,0 +1,334 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, instruction, state::MembershipState};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::InstructionError};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+const PRICE: u64 = 10_000_000;
+
+async fn setup(
+    max_pause_days: Option<u64>,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestMembership,
+    User,
+) {
+    let (user, referrer, market, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, PRICE, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(3),
+                access: Some(30),
+                max_supply: Some(5),
+                price: PRICE,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: PRICE,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    (context, test_pass, test_store, membership, buyer)
+}
+
+async fn set_time(context: &mut ProgramTestContext, unix_timestamp: u64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp as i64;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, test_store, membership, buyer) = setup(Some(14)).await;
+    let before = membership.get_data(&mut context).await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let paused_at = clock.unix_timestamp as u64;
+
+    membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.paused_at, Some(paused_at));
+    assert_eq!(membership_data.pause_ends_at, Some(paused_at + 14 * 86400));
+
+    let result = membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 1)
+        .await;
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipPaused,
+        0
+    );
+
+    set_time(&mut context, paused_at + 7 * 86400).await;
+    membership
+        .resume(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.paused_at, None);
+    assert_eq!(
+        membership_data.expires_at.unwrap(),
+        before.expires_at.unwrap() + 7 * 86400
+    );
+    assert_eq!(
+        test_pass.get_data(&mut context).await.members_expire_at,
+        membership_data.expires_at.unwrap()
+    );
+
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 2)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn success_expire_after_max_pause() {
+    let (mut context, test_pass, test_store, membership, buyer) = setup(Some(14)).await;
+    let before = membership.get_data(&mut context).await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let paused_at = clock.unix_timestamp as u64;
+    let caller = Keypair::new();
+
+    membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    // the pause ends on its own, the time left is kept
+    set_time(&mut context, paused_at + 14 * 86400 + 1).await;
+    let result = membership
+        .expire(&mut context, &test_store.pubkey, &caller.pubkey())
+        .await;
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipStillActive,
+        0
+    );
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 1)
+        .await
+        .unwrap();
+
+    set_time(&mut context, before.expires_at.unwrap() + 14 * 86400 + 1).await;
+    membership
+        .expire(&mut context, &test_store.pubkey, &caller.pubkey())
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.state, MembershipState::Expired);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.active_membership_count, 0);
+}
+
+#[tokio::test]
+async fn success_resume_after_max_pause() {
+    let (mut context, test_pass, test_store, membership, buyer) = setup(Some(14)).await;
+    let before = membership.get_data(&mut context).await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let paused_at = clock.unix_timestamp as u64;
+
+    membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    set_time(&mut context, paused_at + 20 * 86400).await;
+    membership
+        .resume(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.paused_at, None);
+    assert_eq!(membership_data.paused_duration, 14 * 86400);
+    assert_eq!(
+        membership_data.expires_at.unwrap(),
+        before.expires_at.unwrap() + 14 * 86400
+    );
+}
+
+#[tokio::test]
+async fn failure_pause_allowance_used() {
+    let (mut context, test_pass, test_store, membership, buyer) = setup(Some(14)).await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let paused_at = clock.unix_timestamp as u64;
+
+    membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    set_time(&mut context, paused_at + 10 * 86400).await;
+    membership
+        .resume(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+
+    // the second pause only gets what is left of the allowance
+    membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await
+        .unwrap();
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(
+        membership_data.pause_ends_at,
+        Some(paused_at + 14 * 86400)
+    );
+
+    context.warp_to_slot(3).unwrap();
+    set_time(&mut context, paused_at + 15 * 86400).await;
+    let result = membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::PauseAllowanceUsed,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_pause_not_allowed() {
+    let (mut context, test_pass, test_store, membership, buyer) = setup(None).await;
+
+    let result = membership
+        .pause(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::PauseNotAllowed,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_resume_not_paused() {
+    let (mut context, test_pass, test_store, membership, buyer) = setup(Some(14)).await;
+
+    let result = membership
+        .resume(
+            &mut context,
+            &test_pass.pubkey,
+            &test_store.pubkey,
+            &buyer.owner,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipNotPaused,
+        0
+    );
+}
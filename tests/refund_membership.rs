@@ -0,0 +1,230 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, instruction, state::MembershipState};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::InstructionError, program_pack::Pack};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+use utils::*;
+
+async fn setup(
+    refund_window: Option<u64>,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestTradeHistory,
+    TestMembership,
+    User,
+    User,
+    User,
+) {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, 10_000_000, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(3),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_pass,
+        test_store,
+        trade_history,
+        membership,
+        user,
+        market_place_user,
+        buyer,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, test_store, trade_history, membership, user, market, buyer) =
+        setup(Some(3600)).await;
+    let passbook = test_pass.get_data(&mut context).await;
+
+    test_pass
+        .refund(
+            &mut context,
+            &test_store,
+            &buyer,
+            &buyer,
+            &membership,
+            &trade_history,
+        )
+        .await
+        .unwrap();
+
+    let buyer_token_account = get_account(&mut context, &buyer.token_account.pubkey()).await;
+    let buyer_token = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+    assert_eq!(buyer_token.amount, 10_000_000);
+
+    for authority in [user.pubkey(), market.pubkey()] {
+        let payout = TestPayout::new(&authority, &passbook.mint)
+            .get_data(&mut context)
+            .await;
+        assert_eq!(payout.cash_in, 0);
+    }
+
+    let membership = membership.get_data(&mut context).await;
+    assert_eq!(membership.state, MembershipState::Expired);
+    assert_eq!(membership.purchase, None);
+
+    let trade_history = trade_history.get_data(&mut context).await;
+    assert_eq!(trade_history.already_bought, 0);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.active_membership_count, 0);
+    assert_eq!(store.pass_count, 0);
+
+    assert_eq!(test_pass.get_data(&mut context).await.supply, 0);
+}
+
+#[tokio::test]
+async fn success_after_market_config_change() {
+    let (mut context, test_pass, test_store, trade_history, membership, user, market, buyer) =
+        setup(Some(3600)).await;
+    let passbook = test_pass.get_data(&mut context).await;
+
+    let purchase = membership.get_data(&mut context).await.purchase.unwrap();
+    assert_eq!(purchase.mint, passbook.mint);
+    assert_eq!(
+        purchase.payouts.iter().map(|p| p.amount).sum::<u64>(),
+        10_000_000
+    );
+
+    // the refund takes back what the purchase paid, not the split of the new fee
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 1000, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .refund(
+            &mut context,
+            &test_store,
+            &buyer,
+            &buyer,
+            &membership,
+            &trade_history,
+        )
+        .await
+        .unwrap();
+
+    let buyer_token_account = get_account(&mut context, &buyer.token_account.pubkey()).await;
+    let buyer_token = spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+    assert_eq!(buyer_token.amount, 10_000_000);
+
+    for authority in [user.pubkey(), market.pubkey()] {
+        let payout = TestPayout::new(&authority, &passbook.mint)
+            .get_data(&mut context)
+            .await;
+        assert_eq!(payout.cash_in, 0);
+    }
+}
+
+#[tokio::test]
+async fn failure_without_refund_window() {
+    let (mut context, test_pass, test_store, trade_history, membership, _, _, buyer) =
+        setup(None).await;
+
+    let result = test_pass
+        .refund(
+            &mut context,
+            &test_store,
+            &buyer,
+            &buyer,
+            &membership,
+            &trade_history,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::RefundWindowClosed,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_membership_used() {
+    let (mut context, test_pass, test_store, trade_history, membership, _, _, buyer) =
+        setup(Some(3600)).await;
+
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 1)
+        .await
+        .unwrap();
+
+    let result = test_pass
+        .refund(
+            &mut context,
+            &test_store,
+            &buyer,
+            &buyer,
+            &membership,
+            &trade_history,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipAlreadyUsed,
+        0
+    );
+}
@@ -0,0 +1,47 @@
+mod utils;
+
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, test_store, membership, creator, _) = setup_membership(Some(3)).await;
+    let staff = Keypair::new();
+    let store_authority = TestStoreAuthority::new(&test_store.pubkey, &staff.pubkey());
+
+    test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+            5,
+        )
+        .await
+        .unwrap();
+
+    test_store
+        .remove_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    assert!(is_empty_account(&mut context, &store_authority.pubkey).await);
+
+    let result = membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &staff,
+            Some(&store_authority.pubkey),
+            1,
+        )
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,256 @@
+mod utils;
+
+use nft_pass_book::{
+    error::NFTPassError,
+    find_pass_program_address, instruction,
+    state::{MembershipState, Pass},
+};
+use num_traits::FromPrimitive;
+use solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+const PRICE: u64 = 10_000_000;
+
+fn buy_args(mint: Pubkey) -> instruction::BuyPassArgs {
+    instruction::BuyPassArgs {
+        expected_price: PRICE,
+        expected_mint: mint,
+        allowlist_proof: None,
+    }
+}
+
+async fn setup(
+    max_supply: Option<u64>,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestTradeHistory,
+    TestMembership,
+    User,
+    User,
+) {
+    let (user, referrer, market, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, PRICE * 2, false).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(3),
+                access: Some(30),
+                max_supply,
+                price: PRICE,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pass.activate(&mut context, &user).await.unwrap();
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            buy_args(token.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_pass,
+        test_store,
+        trade_history,
+        membership,
+        market,
+        buyer,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, test_store, trade_history, membership, market, buyer) =
+        setup(Some(5)).await;
+
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 1)
+        .await
+        .unwrap();
+
+    let before = membership.get_data(&mut context).await;
+    let mint = test_pass.get_data(&mut context).await.mint;
+
+    test_pass
+        .renew(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            buy_args(mint),
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.state, MembershipState::Activated);
+    assert_eq!(
+        membership_data.expires_at.unwrap(),
+        before.expires_at.unwrap() + 30 * 86400
+    );
+    let uses = membership_data.uses.unwrap();
+    assert_eq!(uses.remaining, 5);
+    assert_eq!(uses.total, 6);
+
+    let passbook = test_pass.get_data(&mut context).await;
+    assert_eq!(passbook.supply, 1);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.pass_count, 2);
+    assert_eq!(store.membership_count, 1);
+    assert_eq!(store.active_membership_count, 1);
+
+    let trade_history_data = trade_history.get_data(&mut context).await;
+    assert_eq!(trade_history_data.already_bought, 2);
+}
+
+#[tokio::test]
+async fn failure_membership_not_activated() {
+    let (mut context, test_pass, test_store, trade_history, membership, market, buyer) =
+        setup(Some(5)).await;
+
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 3)
+        .await
+        .unwrap();
+
+    let mint = test_pass.get_data(&mut context).await.mint;
+    let result = test_pass
+        .renew(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            buy_args(mint),
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::MembershipNotActivated,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_sold_out() {
+    let (mut context, test_pass, test_store, trade_history, membership, market, buyer) =
+        setup(Some(1)).await;
+
+    let mint = test_pass.get_data(&mut context).await.mint;
+    test_pass
+        .renew(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            buy_args(mint),
+        )
+        .await
+        .unwrap();
+
+    let passbook = test_pass.get_data(&mut context).await;
+    assert_eq!(passbook.supply, 1);
+}
+
+#[tokio::test]
+async fn success_pass_expiry() {
+    let (mut context, test_pass, test_store, token, _, buyer, test_metadata) =
+        setup_master_edition_pass_book(false).await;
+    let (membership, pass_mint) = buy_pass_edition(
+        &mut context,
+        &test_pass,
+        &test_store,
+        &token,
+        &buyer,
+        &test_metadata.mint.pubkey(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let mint_authority = context.payer.pubkey();
+    mint_tokens(
+        &mut context,
+        &token.pubkey(),
+        &buyer.token_account.pubkey(),
+        PRICE,
+        &mint_authority,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let trade_history = TestTradeHistory::new(&test_pass.pubkey, &buyer.pubkey());
+    test_pass
+        .renew(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            None,
+            &trade_history,
+            buy_args(token.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // the printed pass follows the renewed expiry
+    let membership_data = membership.get_data(&mut context).await;
+    let (pass_key, _) = find_pass_program_address(&nft_pass_book::id(), &pass_mint.pubkey());
+    let pass_account = get_account(&mut context, &pass_key).await;
+    let pass = Pass::unpack_unchecked(&pass_account.data).unwrap();
+    assert_eq!(pass.expires_at, membership_data.expires_at);
+}
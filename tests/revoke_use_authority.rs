@@ -0,0 +1,153 @@
+mod utils;
+
+use nft_pass_book::error::NFTPassError;
+use num_traits::FromPrimitive;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_associated_token_account::get_associated_token_address;
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, test_store, membership, _, buyer) = setup_membership(Some(3)).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+
+    membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            2,
+        )
+        .await
+        .unwrap();
+
+    membership
+        .revoke_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert!(is_empty_account(&mut context, &use_authority.pubkey).await);
+
+    let result = membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &delegate,
+            Some(&use_authority.pubkey),
+            1,
+        )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn failure_invalid_owner() {
+    let (mut context, _, _, membership, _, buyer) = setup_membership(Some(3)).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+
+    membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            2,
+        )
+        .await
+        .unwrap();
+
+    let result = membership
+        .revoke_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &Keypair::new(),
+            &delegate.pubkey(),
+            None,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidMembershipAuthority,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_closed_membership() {
+    let (mut context, _, test_store, membership, _, buyer, pass_mint) =
+        setup_pass_membership(false).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+    let holder = Keypair::new();
+    let holder_token = Keypair::new();
+
+    membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            2,
+        )
+        .await
+        .unwrap();
+
+    create_token_account(
+        &mut context,
+        &holder_token,
+        &pass_mint.pubkey(),
+        &holder.pubkey(),
+    )
+    .await
+    .unwrap();
+    transfer_token(
+        &mut context,
+        &get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey()),
+        &holder_token.pubkey(),
+        &buyer.owner,
+        1,
+    )
+    .await
+    .unwrap();
+    membership
+        .sync_owner(
+            &mut context,
+            &test_store.pubkey,
+            &pass_mint.pubkey(),
+            &holder_token.pubkey(),
+            &holder,
+            &buyer.pubkey(),
+        )
+        .await
+        .unwrap();
+    assert!(is_empty_account(&mut context, &membership.pubkey).await);
+
+    membership
+        .revoke_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            Some(&test_store.pubkey),
+        )
+        .await
+        .unwrap();
+
+    assert!(is_empty_account(&mut context, &use_authority.pubkey).await);
+}
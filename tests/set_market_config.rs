@@ -0,0 +1,74 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, state::AccountType};
+use num_traits::FromPrimitive;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let mut context = nft_pass_book_program_test().start_with_context().await;
+    let (_, _, market, _) = setup_users();
+    let market_config = TestMarketConfig::new(&market.pubkey());
+
+    market_config
+        .set(&mut context, &market, 250, 50, 10)
+        .await
+        .unwrap();
+
+    let config = market_config.get_data(&mut context).await;
+    assert_eq!(config.account_type, AccountType::MarketConfig);
+    assert_eq!(config.authority, market.pubkey());
+    assert_eq!(config.market_fee_basis_point, 250);
+    assert_eq!(config.referral_share, 50);
+    assert_eq!(config.referral_kick_back_share, 10);
+
+    // the market authority can update its fees later on
+    market_config
+        .set(&mut context, &market, 500, 20, 0)
+        .await
+        .unwrap();
+
+    let config = market_config.get_data(&mut context).await;
+    assert_eq!(config.market_fee_basis_point, 500);
+    assert_eq!(config.referral_share, 20);
+    assert_eq!(config.referral_kick_back_share, 0);
+}
+
+#[tokio::test]
+async fn failure_wrong_basis_point() {
+    let mut context = nft_pass_book_program_test().start_with_context().await;
+    let (_, _, market, _) = setup_users();
+
+    let result = TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 10_001, 50, 0)
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::WrongMarketSellerBasisPoint,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_other_authority() {
+    let mut context = nft_pass_book_program_test().start_with_context().await;
+    let (_, _, market, _) = setup_users();
+    let fake_market = User {
+        owner: Keypair::new(),
+        token_account: Keypair::new(),
+    };
+
+    let result = TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &fake_market, 250, 50, 0)
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidMarketConfigKey,
+        0
+    );
+}
@@ -0,0 +1,256 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, find_pass_book_escrow, instruction};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::InstructionError, program_pack::Pack};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+use utils::*;
+
+async fn setup(
+    is_native: bool,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    User,
+    User,
+    User,
+    User,
+    TestSplToken,
+) {
+    let (user, referrer, market_place_user, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, 20_000_000, is_native).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market_place_user),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: Some(3),
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: Some(1800),
+                escrow_hold_period: Some(3600),
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market_place_user.pubkey())
+        .set(&mut context, &market_place_user, 250, 50, 0)
+        .await
+        .unwrap();
+
+    if !is_native {
+        test_pass
+            .create_escrow_token_account(&mut context)
+            .await
+            .unwrap();
+    }
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market_place_user),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_pass,
+        test_store,
+        user,
+        market_place_user,
+        referrer,
+        buyer,
+        token,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, test_store, user, market, referrer, _, _) = setup(false).await;
+    let passbook = test_pass.get_data(&mut context).await;
+    assert_eq!(passbook.escrow_buckets.len(), 1);
+    assert_eq!(passbook.escrow_buckets[0].referral_amount, 10_000_000);
+
+    let escrow = find_pass_book_escrow(&nft_pass_book::id(), &test_pass.pubkey).0;
+    let escrow_token = test_pass.escrow_token_account(&passbook).unwrap();
+    let escrow_token_account = get_account(&mut context, &escrow_token).await;
+    let escrow_token_data = spl_token::state::Account::unpack(&escrow_token_account.data).unwrap();
+    assert_eq!(escrow_token_data.owner, escrow);
+    assert_eq!(escrow_token_data.amount, 10_000_000);
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = passbook.escrow_buckets[0].release_at as i64;
+    context.set_sysvar(&clock);
+
+    test_pass
+        .settle(&mut context, &test_store, Some(&market))
+        .await
+        .unwrap();
+
+    let escrow_token_account = get_account(&mut context, &escrow_token).await;
+    let escrow_token_data = spl_token::state::Account::unpack(&escrow_token_account.data).unwrap();
+    assert_eq!(escrow_token_data.amount, 0);
+
+    let creator_payout = TestPayout::new(&user.pubkey(), &passbook.mint)
+        .get_data(&mut context)
+        .await;
+    let market_payout = TestPayout::new(&market.pubkey(), &passbook.mint)
+        .get_data(&mut context)
+        .await;
+    let referrer_payout = TestPayout::new(&referrer.pubkey(), &passbook.mint)
+        .get_data(&mut context)
+        .await;
+    assert_eq!(creator_payout.cash_in, 9_750_000);
+    assert_eq!(market_payout.cash_in, 125_000);
+    assert_eq!(referrer_payout.cash_in, 125_000);
+
+    let passbook = test_pass.get_data(&mut context).await;
+    assert!(passbook.escrow_buckets.is_empty());
+}
+
+#[tokio::test]
+async fn success_late_purchase_does_not_delay_settlement() {
+    let (mut context, test_pass, test_store, _, market, _, buyer, token) = setup(false).await;
+    let passbook = test_pass.get_data(&mut context).await;
+    let first_release_at = passbook.escrow_buckets[0].release_at;
+
+    // a purchase just before the first release is held until its own release
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = (first_release_at - 600) as i64;
+    context.set_sysvar(&clock);
+
+    let beneficiary = Keypair::new();
+    test_pass
+        .gift(
+            &mut context,
+            &test_store,
+            &buyer,
+            &beneficiary.pubkey(),
+            &TestMembership::new(&test_store.pubkey, &beneficiary.pubkey()),
+            Some(&market),
+            &TestTradeHistory::new(&test_pass.pubkey, &buyer.pubkey()),
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let passbook = test_pass.get_data(&mut context).await;
+    assert_eq!(passbook.escrow_buckets.len(), 2);
+    assert_eq!(passbook.escrow_buckets[0].release_at, first_release_at);
+    assert!(passbook.escrow_buckets[1].release_at > first_release_at);
+
+    clock.unix_timestamp = first_release_at as i64;
+    context.set_sysvar(&clock);
+
+    test_pass
+        .settle(&mut context, &test_store, Some(&market))
+        .await
+        .unwrap();
+
+    let escrow_token = test_pass.escrow_token_account(&passbook).unwrap();
+    let escrow_token_account = get_account(&mut context, &escrow_token).await;
+    let escrow_token_data = spl_token::state::Account::unpack(&escrow_token_account.data).unwrap();
+    assert_eq!(escrow_token_data.amount, 10_000_000);
+
+    let passbook = test_pass.get_data(&mut context).await;
+    assert_eq!(passbook.escrow_buckets.len(), 1);
+    assert!(passbook.escrow_buckets[0].release_at > first_release_at);
+}
+
+#[tokio::test]
+async fn success_native_leaves_rent_exempt_escrow() {
+    let (mut context, test_pass, test_store, _, market, _, _, _) = setup(true).await;
+    let passbook = test_pass.get_data(&mut context).await;
+
+    // the referral kick back does not split evenly, the dust goes out of the escrow as well
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 333, 33, 33)
+        .await
+        .unwrap();
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = passbook.escrow_buckets[0].release_at as i64;
+    context.set_sysvar(&clock);
+
+    test_pass
+        .settle(&mut context, &test_store, Some(&market))
+        .await
+        .unwrap();
+
+    let escrow = find_pass_book_escrow(&nft_pass_book::id(), &test_pass.pubkey).0;
+    let escrow_account = get_account(&mut context, &escrow).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert_eq!(escrow_account.lamports, rent.minimum_balance(0));
+}
+
+#[tokio::test]
+async fn failure_locked() {
+    let (mut context, test_pass, test_store, _, market, _, _, _) = setup(false).await;
+
+    let result = test_pass
+        .settle(&mut context, &test_store, Some(&market))
+        .await;
+
+    assert_custom_error!(result.unwrap_err().unwrap(), NFTPassError::EscrowLocked, 0);
+}
+
+#[tokio::test]
+async fn failure_empty() {
+    let (mut context, test_pass, test_store, _, market, _, _, _) = setup(false).await;
+    let passbook = test_pass.get_data(&mut context).await;
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = passbook.escrow_buckets[0].release_at as i64;
+    context.set_sysvar(&clock);
+
+    test_pass
+        .settle(&mut context, &test_store, Some(&market))
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    let result = test_pass
+        .settle(&mut context, &test_store, Some(&market))
+        .await;
+
+    assert_custom_error!(result.unwrap_err().unwrap(), NFTPassError::EscrowEmpty, 0);
+}
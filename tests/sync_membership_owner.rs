@@ -0,0 +1,266 @@
+mod utils;
+
+use nft_pass_book::{
+    error::NFTPassError,
+    find_pass_program_address, instruction,
+    state::{MembershipState, Pass},
+};
+use num_traits::FromPrimitive;
+use solana_program::{clock::Clock, instruction::InstructionError, program_pack::Pack};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::{Account, AccountState};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, test_store, membership, _, buyer, pass_mint) =
+        setup_pass_membership(false).await;
+    let holder = Keypair::new();
+    let holder_token = Keypair::new();
+
+    create_token_account(
+        &mut context,
+        &holder_token,
+        &pass_mint.pubkey(),
+        &holder.pubkey(),
+    )
+    .await
+    .unwrap();
+    transfer_token(
+        &mut context,
+        &get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey()),
+        &holder_token.pubkey(),
+        &buyer.owner,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let previous = membership.get_data(&mut context).await;
+
+    membership
+        .sync_owner(
+            &mut context,
+            &test_store.pubkey,
+            &pass_mint.pubkey(),
+            &holder_token.pubkey(),
+            &holder,
+            &buyer.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    assert!(is_empty_account(&mut context, &membership.pubkey).await);
+
+    let holder_membership = TestMembership::new(&test_store.pubkey, &holder.pubkey());
+    let holder_data = holder_membership.get_data(&mut context).await;
+    assert_eq!(holder_data.owner, holder.pubkey());
+    assert_eq!(holder_data.state, MembershipState::Activated);
+    assert_eq!(holder_data.pass, previous.pass);
+    assert_eq!(holder_data.passbook, previous.passbook);
+    assert_eq!(holder_data.expires_at, previous.expires_at);
+
+    let (pass_key, _) = find_pass_program_address(&nft_pass_book::id(), &pass_mint.pubkey());
+    let pass_account = get_account(&mut context, &pass_key).await;
+    let pass = Pass::unpack_unchecked(&pass_account.data).unwrap();
+    assert_eq!(pass.owner, holder.pubkey());
+    assert_eq!(pass.expires_at, holder_data.expires_at);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.membership_count, 1);
+    assert_eq!(store.active_membership_count, 1);
+}
+
+#[tokio::test]
+async fn failure_not_pass_holder() {
+    let (mut context, _, test_store, membership, _, buyer, pass_mint) =
+        setup_pass_membership(false).await;
+    let holder = Keypair::new();
+    let holder_token = Keypair::new();
+
+    create_token_account(
+        &mut context,
+        &holder_token,
+        &pass_mint.pubkey(),
+        &holder.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let result = membership
+        .sync_owner(
+            &mut context,
+            &test_store.pubkey,
+            &pass_mint.pubkey(),
+            &holder_token.pubkey(),
+            &holder,
+            &buyer.pubkey(),
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::NotPassHolder,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_refreeze_soulbound_pass() {
+    let (mut context, test_pass, test_store, membership, user, buyer, pass_mint) =
+        setup_pass_membership(true).await;
+    let pass_token = get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey());
+    let holder = Keypair::new();
+    let holder_token = Keypair::new();
+
+    create_token_account(
+        &mut context,
+        &holder_token,
+        &pass_mint.pubkey(),
+        &holder.pubkey(),
+    )
+    .await
+    .unwrap();
+    test_pass
+        .thaw_pass(&mut context, &user, &pass_mint.pubkey(), &pass_token)
+        .await
+        .unwrap();
+    transfer_token(
+        &mut context,
+        &pass_token,
+        &holder_token.pubkey(),
+        &buyer.owner,
+        1,
+    )
+    .await
+    .unwrap();
+
+    membership
+        .sync_owner(
+            &mut context,
+            &test_store.pubkey,
+            &pass_mint.pubkey(),
+            &holder_token.pubkey(),
+            &holder,
+            &buyer.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let token_account = get_account(&mut context, &holder_token.pubkey()).await;
+    let token = Account::unpack(&token_account.data).unwrap();
+    assert_eq!(token.state, AccountState::Frozen);
+}
+
+#[tokio::test]
+async fn failure_membership_moved_to_other_pass_book() {
+    let (mut context, _, test_store, membership, user, buyer, pass_mint) =
+        setup_pass_membership(false).await;
+
+    // the membership lapses and is bought again from a pass book without a master edition
+    let expires_at = membership.get_data(&mut context).await.expires_at.unwrap();
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = expires_at as i64 + 1;
+    context.set_sysvar(&clock);
+
+    let native = TestSplToken::new(true);
+    native
+        .airdrop(&mut context, 10_000_000, &buyer.pubkey())
+        .await
+        .unwrap();
+    let other_pass = TestPassBook::new();
+    other_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &native.pubkey(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Other Pass"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: None,
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+    other_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            None,
+            &TestTradeHistory::new(&other_pass.pubkey, &buyer.pubkey()),
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: native.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(membership.get_data(&mut context).await.pass, None);
+
+    // the old pass no longer carries the membership
+    let holder = Keypair::new();
+    let holder_token = Keypair::new();
+    create_token_account(
+        &mut context,
+        &holder_token,
+        &pass_mint.pubkey(),
+        &holder.pubkey(),
+    )
+    .await
+    .unwrap();
+    transfer_token(
+        &mut context,
+        &get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey()),
+        &holder_token.pubkey(),
+        &buyer.owner,
+        1,
+    )
+    .await
+    .unwrap();
+
+    let result = membership
+        .sync_owner(
+            &mut context,
+            &test_store.pubkey,
+            &pass_mint.pubkey(),
+            &holder_token.pubkey(),
+            &holder,
+            &buyer.pubkey(),
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidMembershipKey,
+        0
+    );
+}
@@ -0,0 +1,105 @@
+mod utils;
+
+use nft_pass_book::state::{MemberRefundArgs, PassBookRefundArgs};
+use solana_program::program_pack::Pack;
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::{Account, AccountState};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pass, _, _, user, buyer, pass_mint) =
+        setup_pass_membership(true).await;
+    let pass_token = get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey());
+
+    let token_account = get_account(&mut context, &pass_token).await;
+    let token = Account::unpack(&token_account.data).unwrap();
+    assert_eq!(token.state, AccountState::Frozen);
+
+    let holder = Keypair::new();
+    let holder_token = Keypair::new();
+    create_token_account(
+        &mut context,
+        &holder_token,
+        &pass_mint.pubkey(),
+        &holder.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    transfer_token(
+        &mut context,
+        &pass_token,
+        &holder_token.pubkey(),
+        &buyer.owner,
+        1,
+    )
+    .await
+    .unwrap_err();
+
+    test_pass
+        .thaw_pass(&mut context, &user, &pass_mint.pubkey(), &pass_token)
+        .await
+        .unwrap();
+
+    let token_account = get_account(&mut context, &pass_token).await;
+    let token = Account::unpack(&token_account.data).unwrap();
+    assert_eq!(token.state, AccountState::Initialized);
+
+    transfer_token(
+        &mut context,
+        &pass_token,
+        &holder_token.pubkey(),
+        &buyer.owner,
+        1,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn failure_not_authority() {
+    let (mut context, test_pass, _, _, _, buyer, pass_mint) = setup_pass_membership(true).await;
+    let pass_token = get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey());
+
+    let result = test_pass
+        .thaw_pass(&mut context, &buyer, &pass_mint.pubkey(), &pass_token)
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn success_holder_after_pass_book_deleted() {
+    let (mut context, test_pass, _, membership, user, buyer, pass_mint) =
+        setup_pass_membership(true).await;
+    let pass_token = get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey());
+
+    test_pass
+        .delete_with_refunds(
+            &mut context,
+            &user,
+            &user.pubkey(),
+            Some(&PassBookRefundArgs {
+                source_token_account: user.token_account.pubkey(),
+                token_program: spl_token::id(),
+                members: vec![MemberRefundArgs {
+                    membership: membership.pubkey,
+                    token_account: buyer.token_account.pubkey(),
+                }],
+            }),
+        )
+        .await
+        .unwrap();
+
+    test_pass
+        .thaw_pass(&mut context, &buyer, &pass_mint.pubkey(), &pass_token)
+        .await
+        .unwrap();
+
+    let token_account = get_account(&mut context, &pass_token).await;
+    let token = Account::unpack(&token_account.data).unwrap();
+    assert_eq!(token.state, AccountState::Initialized);
+}
@@ -0,0 +1,235 @@
+mod utils;
+
+use nft_pass_book::{error::NFTPassError, state::MembershipState};
+use num_traits::FromPrimitive;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let (mut context, _, test_store, membership, _, buyer) = setup_membership(Some(3)).await;
+
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 1)
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    let uses = membership_data.uses.unwrap();
+    assert_eq!(uses.remaining, 2);
+    assert_eq!(uses.total, 3);
+    assert_eq!(membership_data.state, MembershipState::Activated);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.redemptions_count, 1);
+    assert_eq!(store.active_membership_count, 1);
+}
+
+#[tokio::test]
+async fn success_expire_when_uses_run_out() {
+    let (mut context, _, test_store, membership, _, buyer) = setup_membership(Some(3)).await;
+
+    membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 3)
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.uses.unwrap().remaining, 0);
+    assert_eq!(membership_data.state, MembershipState::Expired);
+
+    let store = test_store.get_data(&mut context).await;
+    assert_eq!(store.redemptions_count, 1);
+    assert_eq!(store.active_membership_count, 0);
+}
+
+#[tokio::test]
+async fn failure_not_enough_uses() {
+    let (mut context, _, test_store, membership, _, buyer) = setup_membership(Some(3)).await;
+
+    let result = membership
+        .use_membership(&mut context, &test_store.pubkey, &buyer.owner, None, 4)
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::NotEnoughUses,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_invalid_authority() {
+    let (mut context, _, test_store, membership, _, _) = setup_membership(Some(3)).await;
+
+    let result = membership
+        .use_membership(&mut context, &test_store.pubkey, &Keypair::new(), None, 1)
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidMembershipAuthority,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_with_use_authority() {
+    let (mut context, _, test_store, membership, _, buyer) = setup_membership(Some(3)).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+
+    membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            2,
+        )
+        .await
+        .unwrap();
+
+    membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &delegate,
+            Some(&use_authority.pubkey),
+            1,
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.uses.unwrap().remaining, 2);
+
+    let use_authority_data = use_authority.get_data(&mut context).await;
+    assert_eq!(use_authority_data.allowed_uses, 1);
+}
+
+#[tokio::test]
+async fn failure_not_enough_allowed_uses() {
+    let (mut context, _, test_store, membership, _, buyer) = setup_membership(Some(3)).await;
+    let delegate = Keypair::new();
+    let use_authority = TestUseAuthority::new(&membership.pubkey, &delegate.pubkey());
+
+    membership
+        .approve_use_authority(
+            &mut context,
+            &use_authority.pubkey,
+            &buyer.owner,
+            &delegate.pubkey(),
+            1,
+        )
+        .await
+        .unwrap();
+
+    let result = membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &delegate,
+            Some(&use_authority.pubkey),
+            2,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::NotEnoughAllowedUses,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_with_store_authority() {
+    let (mut context, _, test_store, membership, creator, _) = setup_membership(Some(3)).await;
+    let staff = Keypair::new();
+    let store_authority = TestStoreAuthority::new(&test_store.pubkey, &staff.pubkey());
+
+    test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+            2,
+        )
+        .await
+        .unwrap();
+
+    membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &staff,
+            Some(&store_authority.pubkey),
+            2,
+        )
+        .await
+        .unwrap();
+
+    let membership_data = membership.get_data(&mut context).await;
+    assert_eq!(membership_data.uses.unwrap().remaining, 1);
+
+    let store_authority_data = store_authority.get_data(&mut context).await;
+    assert_eq!(store_authority_data.allowed_redemptions, 0);
+
+    context.warp_to_slot(3).unwrap();
+
+    let result = membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &staff,
+            Some(&store_authority.pubkey),
+            1,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::NotEnoughAllowedRedemptions,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_not_enough_allowed_redemptions() {
+    let (mut context, _, test_store, membership, creator, _) = setup_membership(Some(3)).await;
+    let staff = Keypair::new();
+    let store_authority = TestStoreAuthority::new(&test_store.pubkey, &staff.pubkey());
+
+    test_store
+        .add_store_authority(
+            &mut context,
+            &store_authority.pubkey,
+            &creator.owner,
+            &staff.pubkey(),
+            1,
+        )
+        .await
+        .unwrap();
+
+    let result = membership
+        .use_membership(
+            &mut context,
+            &test_store.pubkey,
+            &staff,
+            Some(&store_authority.pubkey),
+            2,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::NotEnoughAllowedRedemptions,
+        0
+    );
+}
@@ -0,0 +1,55 @@
+use crate::*;
+use nft_pass_book::{
+    find_market_config_program_address,
+    instruction::{self, SetMarketConfigArgs},
+    state::MarketConfig,
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{signer::Signer, transaction::Transaction};
+
+#[derive(Debug)]
+pub struct TestMarketConfig {
+    pub pubkey: Pubkey,
+}
+
+impl TestMarketConfig {
+    pub fn new(authority: &Pubkey) -> Self {
+        let (pubkey, _) = find_market_config_program_address(&nft_pass_book::id(), authority);
+
+        TestMarketConfig { pubkey }
+    }
+
+    pub async fn get_data(&self, context: &mut ProgramTestContext) -> MarketConfig {
+        let account = get_account(context, &self.pubkey).await;
+        MarketConfig::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn set(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &User,
+        market_fee_basis_point: u16,
+        referral_share: u8,
+        referral_kick_back_share: u8,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_market_config(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &authority.pubkey(),
+                &context.payer.pubkey(),
+                SetMarketConfigArgs {
+                    market_fee_basis_point,
+                    referral_share,
+                    referral_kick_back_share,
+                },
+            )],
+            Some(&context.payer.pubkey()),
+            &[&authority.owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+}
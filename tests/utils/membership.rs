@@ -1,7 +1,13 @@
-use nft_pass_book::{state::Membership, find_membership_program_address};
+use nft_pass_book::{
+    find_membership_program_address, find_pass_program_address,
+    instruction::{self, ApproveUseAuthorityArgs, UseMembershipArgs},
+    state::{Membership, Pass, PassBook},
+};
 use solana_program::program_pack::Pack;
-use solana_program_test::ProgramTestContext;
-use solana_sdk::pubkey::Pubkey;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Signer, signer::keypair::Keypair, transaction::Transaction,
+};
 
 use super::get_account;
 
@@ -22,4 +28,212 @@ impl TestMembership {
         let account = get_account(context, &self.pubkey).await;
         Membership::unpack_unchecked(&account.data).unwrap()
     }
+
+    /// Pass book the membership refers to, none before the membership is created
+    pub async fn get_passbook(&self, context: &mut ProgramTestContext) -> Option<Pubkey> {
+        let account = context
+            .banks_client
+            .get_account(self.pubkey)
+            .await
+            .unwrap()?;
+        Membership::unpack_unchecked(&account.data).unwrap().passbook
+    }
+
+    /// Mint of the pass printed for the membership, none without a pass
+    pub async fn get_pass_mint(&self, context: &mut ProgramTestContext) -> Option<Pubkey> {
+        let account = context
+            .banks_client
+            .get_account(self.pubkey)
+            .await
+            .unwrap()?;
+        let pass = Membership::unpack_unchecked(&account.data).unwrap().pass?;
+        let pass_account = get_account(context, &pass).await;
+        Some(Pass::unpack_unchecked(&pass_account.data).unwrap().mint)
+    }
+
+    pub async fn use_membership(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &Pubkey,
+        authority: &Keypair,
+        use_authority_record: Option<&Pubkey>,
+        count: u64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::use_membership(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                store,
+                &authority.pubkey(),
+                use_authority_record,
+                UseMembershipArgs { count },
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn pause(
+        &self,
+        context: &mut ProgramTestContext,
+        passbook: &Pubkey,
+        store: &Pubkey,
+        owner: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let pass_mint = self.get_pass_mint(context).await;
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::pause_membership(
+                &nft_pass_book::id(),
+                passbook,
+                store,
+                &owner.pubkey(),
+                pass_mint.as_ref(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn resume(
+        &self,
+        context: &mut ProgramTestContext,
+        passbook: &Pubkey,
+        store: &Pubkey,
+        owner: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let pass_mint = self.get_pass_mint(context).await;
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::resume_membership(
+                &nft_pass_book::id(),
+                passbook,
+                store,
+                &owner.pubkey(),
+                pass_mint.as_ref(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn approve_use_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        use_authority_record: &Pubkey,
+        owner: &Keypair,
+        user: &Pubkey,
+        number_of_uses: u64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::approve_use_authority(
+                &nft_pass_book::id(),
+                use_authority_record,
+                &self.pubkey,
+                &owner.pubkey(),
+                user,
+                &context.payer.pubkey(),
+                ApproveUseAuthorityArgs { number_of_uses },
+            )],
+            Some(&context.payer.pubkey()),
+            &[owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn revoke_use_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        use_authority_record: &Pubkey,
+        owner: &Keypair,
+        user: &Pubkey,
+        closed_membership_store: Option<&Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::revoke_use_authority(
+                &nft_pass_book::id(),
+                use_authority_record,
+                &self.pubkey,
+                &owner.pubkey(),
+                user,
+                closed_membership_store,
+            )],
+            Some(&context.payer.pubkey()),
+            &[owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn expire(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &Pubkey,
+        caller: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::expire_membership(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                store,
+                caller,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn sync_owner(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &Pubkey,
+        pass_mint: &Pubkey,
+        token_account: &Pubkey,
+        holder: &Keypair,
+        previous_owner: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (pass, _) = find_pass_program_address(&nft_pass_book::id(), pass_mint);
+        let pass = Pass::unpack_unchecked(&get_account(context, &pass).await.data).unwrap();
+        let passbook = get_account(context, &pass.pass_book).await;
+        let soulbound = PassBook::unpack_unchecked(&passbook.data).unwrap().soulbound;
+        let replaced_passbook = TestMembership::new(store, &holder.pubkey())
+            .get_passbook(context)
+            .await;
+        let mut signers = vec![&context.payer];
+        if soulbound {
+            signers.push(holder);
+        }
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::sync_membership_owner(
+                &nft_pass_book::id(),
+                pass_mint,
+                token_account,
+                &holder.pubkey(),
+                previous_owner,
+                store,
+                &context.payer.pubkey(),
+                &pass.pass_book,
+                soulbound,
+                replaced_passbook.as_ref(),
+            )],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
 }
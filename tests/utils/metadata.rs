@@ -161,4 +161,27 @@ impl TestMetadata {
 
         Ok(context.banks_client.process_transaction(tx).await?)
     }
+
+    pub async fn set_update_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        new_update_authority: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::update_metadata_accounts_v2(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                Some(*new_update_authority),
+                None,
+                None,
+                None,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
 }
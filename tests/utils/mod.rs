@@ -1,26 +1,32 @@
 mod assert;
 mod edition;
 mod edition_marker;
+mod market_config;
 mod master_edition_v2;
 mod membership;
 mod metadata;
 mod pass_book;
 mod payout;
 mod store;
+mod store_authority;
 mod token;
 mod trade_history;
+mod use_authority;
 mod user;
 
 pub use assert::*;
 pub use edition_marker::TestEditionMarker;
+pub use market_config::TestMarketConfig;
 pub use master_edition_v2::TestMasterEditionV2;
 pub use membership::TestMembership;
 pub use metadata::TestMetadata;
 pub use pass_book::TestPassBook;
 pub use payout::TestPayout;
 pub use store::TestStore;
+pub use store_authority::TestStoreAuthority;
 pub use token::TestSplToken;
 pub use trade_history::TestTradeHistory;
+pub use use_authority::TestUseAuthority;
 pub use user::*;
 
 use solana_program_test::*;
@@ -29,7 +35,11 @@ use solana_sdk::{
     signer::keypair::Keypair, system_instruction, transaction::Transaction,
 };
 use nft_pass_book::{
-    instruction,
+    find_program_authority, instruction,
+    state::PassMintArgs,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
 };
 use spl_token::state::Mint;
 
@@ -300,11 +310,293 @@ pub async fn setup_pass_book(mutable: bool) -> (ProgramTestContext, TestPassBook
                 price: 10_000_000,
                 has_referrer: referrer.is_some(), // Some(referrer.pubkey()),
                 has_market_authority: market_place_user.is_some(),
+                has_master_edition: false,
                 referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
             },
         )
         .await
         .unwrap();
 
     (context, test_pass, user)
-}
\ No newline at end of file
+}
+pub async fn setup_membership(
+    max_uses: Option<u64>,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestMembership,
+    User,
+    User,
+) {
+    setup_membership_with_mint(max_uses, false).await
+}
+
+pub async fn setup_membership_with_mint(
+    max_uses: Option<u64>,
+    is_native: bool,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestMembership,
+    User,
+    User,
+) {
+    let (user, referrer, market, buyer) = setup_users();
+    let (mut context, test_pass, test_store, trade_history, token, membership) =
+        set_up_pass_book_data(&user, &buyer, 10_000_000, is_native).await;
+
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            Some(&market),
+            Some(&referrer),
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses,
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: true,
+                has_market_authority: true,
+                has_master_edition: false,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound: false,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    TestMarketConfig::new(&market.pubkey())
+        .set(&mut context, &market, 250, 50, 0)
+        .await
+        .unwrap();
+
+    test_pass
+        .buy(
+            &mut context,
+            &test_store,
+            &buyer,
+            &membership,
+            Some(&market),
+            &trade_history,
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    (context, test_pass, test_store, membership, user, buyer)
+}
+
+pub async fn setup_pass_membership(soulbound: bool) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestMembership,
+    User,
+    User,
+    Keypair,
+) {
+    let (mut context, test_pass, test_store, token, user, buyer, test_metadata) =
+        setup_master_edition_pass_book(soulbound).await;
+    let (membership, pass_mint) = buy_pass_edition(
+        &mut context,
+        &test_pass,
+        &test_store,
+        &token,
+        &buyer,
+        &test_metadata.mint.pubkey(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    (context, test_pass, test_store, membership, user, buyer, pass_mint)
+}
+
+pub async fn setup_master_edition_pass_book(
+    soulbound: bool,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestSplToken,
+    User,
+    User,
+    TestMetadata,
+) {
+    setup_master_edition_pass_book_with_metadata(soulbound, true).await
+}
+
+/// Set up a pass book vaulting a master edition whose update authority is the program authority
+pub async fn setup_master_edition_pass_book_with_metadata(
+    soulbound: bool,
+    mutable_metadata: bool,
+) -> (
+    ProgramTestContext,
+    TestPassBook,
+    TestStore,
+    TestSplToken,
+    User,
+    User,
+    TestMetadata,
+) {
+    let (user, _, _, buyer) = setup_users();
+    let (mut context, _, test_store, _, token, _) =
+        set_up_pass_book_data(&user, &buyer, 10_000_000, false).await;
+
+    let test_metadata = TestMetadata::new();
+    test_metadata
+        .create(
+            &mut context,
+            String::from("Pass"),
+            String::from("PASS"),
+            String::from("some link to storage"),
+            None,
+            10,
+            mutable_metadata,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    TestMasterEditionV2::new(&test_metadata)
+        .create(&mut context, None)
+        .await
+        .unwrap();
+    let (program_authority, _) = find_program_authority(&nft_pass_book::id());
+    test_metadata
+        .set_update_authority(&mut context, &program_authority)
+        .await
+        .unwrap();
+
+    let test_pass = TestPassBook::new_with_master_edition(&test_metadata);
+    test_pass
+        .init(
+            &mut context,
+            &user,
+            &test_store.pubkey,
+            &token.pubkey(),
+            None,
+            None,
+            instruction::InitPassBookArgs {
+                name: String::from("Pass Name"),
+                uri: String::from("some link to storage"),
+                description: String::from("Pack description"),
+                mutable: true,
+                max_uses: None,
+                access: Some(30),
+                max_supply: Some(5),
+                price: 10_000_000,
+                has_referrer: false,
+                has_market_authority: false,
+                has_master_edition: true,
+                referral_end_date: None,
+                creators: None,
+                max_per_wallet: None,
+                sale_start: None,
+                sale_end: None,
+                allowlist_root: None,
+                soulbound,
+                refund_window: None,
+                escrow_hold_period: None,
+                max_pause_days: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    (context, test_pass, test_store, token, user, buyer, test_metadata)
+}
+
+/// Buy a pass printed to a mint created by the buyer, returns the buyer membership and pass mint
+pub async fn buy_pass_edition(
+    context: &mut ProgramTestContext,
+    test_pass: &TestPassBook,
+    test_store: &TestStore,
+    token: &TestSplToken,
+    buyer: &User,
+    master_edition_mint: &Pubkey,
+    collection_mint: Option<Pubkey>,
+) -> Result<(TestMembership, Keypair), BanksClientError> {
+    let membership = TestMembership::new(&test_store.pubkey, &buyer.pubkey());
+    let trade_history = TestTradeHistory::new(&test_pass.pubkey, &buyer.pubkey());
+
+    // the buyer mints the token the edition is printed to
+    let pass_mint = Keypair::new();
+    create_mint(context, &pass_mint, &buyer.pubkey(), Some(&buyer.pubkey())).await?;
+    let pass_token = get_associated_token_address(&buyer.pubkey(), &pass_mint.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[create_associated_token_account(
+            &context.payer.pubkey(),
+            &buyer.pubkey(),
+            &pass_mint.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+    mint_tokens(
+        context,
+        &pass_mint.pubkey(),
+        &pass_token,
+        1,
+        &buyer.pubkey(),
+        Some(vec![&buyer.owner]),
+    )
+    .await?;
+
+    let edition = test_pass.get_data(context).await.supply + 1;
+    test_pass
+        .buy_with_pass(
+            context,
+            test_store,
+            buyer,
+            &membership,
+            None,
+            &trade_history,
+            Some(&PassMintArgs {
+                mint: pass_mint.pubkey(),
+                master_edition_mint: *master_edition_mint,
+                edition,
+                token_account: pass_token,
+                collection_mint,
+            }),
+            instruction::BuyPassArgs {
+                expected_price: 10_000_000,
+                expected_mint: token.pubkey(),
+                allowlist_proof: None,
+            },
+        )
+        .await?;
+
+    Ok((membership, pass_mint))
+}
@@ -1,8 +1,11 @@
 use crate::*;
 use nft_pass_book::{
-    find_payout_program_address,
+    find_pass_book_escrow, find_pass_book_program_address, find_payout_program_address,
     instruction::{self, EditPassBookArgs},
-    state::{PassBook, PayoutInfoArgs, Store},
+    state::{
+        Creator, MasterEditionArgs, PassBook, PassBookRefundArgs, PassMintArgs, PayoutInfoArgs,
+        Store,
+    },
     utils::cmp_pubkeys,
 };
 use solana_program::{
@@ -17,13 +20,30 @@ use spl_associated_token_account::{
 #[derive(Debug)]
 pub struct TestPassBook {
     pub account: Keypair,
+    pub pubkey: Pubkey,
+    pub master_edition: Option<MasterEditionArgs>,
 }
 
 impl TestPassBook {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        let account = Keypair::new();
+        Self {
+            pubkey: account.pubkey(),
+            account,
+            master_edition: None,
+        }
+    }
+
+    pub fn new_with_master_edition(metadata: &TestMetadata) -> Self {
+        let mint = metadata.mint.pubkey();
         Self {
             account: Keypair::new(),
+            pubkey: find_pass_book_program_address(&nft_pass_book::id(), &mint).0,
+            master_edition: Some(MasterEditionArgs {
+                mint,
+                token_account: metadata.token.pubkey(),
+            }),
         }
     }
 
@@ -35,7 +55,7 @@ impl TestPassBook {
         let tx = Transaction::new_signed_with_payer(
             &[instruction::activate_pass_book(
                 &nft_pass_book::id(),
-                &self.account.pubkey(),
+                &self.pubkey,
                 &user.owner.pubkey(),
             )],
             Some(&context.payer.pubkey()),
@@ -54,7 +74,7 @@ impl TestPassBook {
         let tx = Transaction::new_signed_with_payer(
             &[instruction::deactivate_pass_book(
                 &nft_pass_book::id(),
-                &self.account.pubkey(),
+                &self.pubkey,
                 &user.owner.pubkey(),
             )],
             Some(&context.payer.pubkey()),
@@ -66,7 +86,7 @@ impl TestPassBook {
     }
 
     pub async fn get_data(&self, context: &mut ProgramTestContext) -> PassBook {
-        let account = get_account(context, &self.account.pubkey()).await;
+        let account = get_account(context, &self.pubkey).await;
         PassBook::unpack_unchecked(&account.data).unwrap()
     }
 
@@ -81,21 +101,69 @@ impl TestPassBook {
         price: Option<u64>,
         blur_hash: Option<String>,
         mint: Option<&Pubkey>,
+        creators: Option<Vec<Creator>>,
     ) -> Result<(), BanksClientError> {
+        self.edit_with_args(
+            context,
+            user,
+            mint,
+            EditPassBookArgs {
+                name,
+                description,
+                uri,
+                price,
+                mutable,
+                creators,
+                ..EditPassBookArgs::default()
+            },
+        )
+        .await
+    }
+
+    pub async fn edit_with_args(
+        &self,
+        context: &mut ProgramTestContext,
+        user: &User,
+        mint: Option<&Pubkey>,
+        args: EditPassBookArgs,
+    ) -> Result<(), BanksClientError> {
+        let passbook = self.get_data(context).await;
+        let payout_mint = *mint.unwrap_or(&passbook.mint);
+        let mut instructions: Vec<Instruction> = vec![];
+        let creator_payouts = match &args.creators {
+            Some(creators) => {
+                let addresses: Vec<Pubkey> = creators.iter().map(|c| c.address).collect();
+                creator_payouts(&addresses, &payout_mint)
+            }
+            None if mint.is_some() => {
+                let addresses: Vec<Pubkey> = passbook.creators.iter().map(|c| c.address).collect();
+                creator_payouts(&addresses, &payout_mint)
+            }
+            None => vec![],
+        };
+        if !cmp_pubkeys(&payout_mint, &spl_token::native_mint::id()) {
+            for creator_payout in creator_payouts.iter() {
+                if is_empty_account(context, &creator_payout.token_account).await {
+                    instructions.push(create_associated_token_account(
+                        &context.payer.pubkey(),
+                        &creator_payout.payout_account,
+                        &payout_mint,
+                    ));
+                }
+            }
+        }
+        instructions.push(instruction::edit_pass_book(
+            &nft_pass_book::id(),
+            &self.pubkey,
+            &user.owner.pubkey(),
+            &context.payer.pubkey(),
+            self.master_edition.as_ref().map(|master_edition| &master_edition.mint),
+            &creator_payouts,
+            mint,
+            args,
+        ));
         let tx = Transaction::new_signed_with_payer(
-            &[instruction::edit_pass_book(
-                &nft_pass_book::id(),
-                &self.account.pubkey(),
-                &user.owner.pubkey(),
-                mint,
-                EditPassBookArgs {
-                    name,
-                    description,
-                    uri,
-                    price,
-                    mutable,
-                },
-            )],
+            &instructions,
             Some(&context.payer.pubkey()),
             &[&user.owner, &context.payer],
             context.last_blockhash,
@@ -109,13 +177,48 @@ impl TestPassBook {
         context: &mut ProgramTestContext,
         user: &User,
         refunder: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        self.delete_with_refunds(context, user, refunder, None).await
+    }
+
+    pub async fn delete_with_refunds(
+        &self,
+        context: &mut ProgramTestContext,
+        user: &User,
+        refunder: &Pubkey,
+        refunds: Option<&PassBookRefundArgs>,
     ) -> Result<(), BanksClientError> {
         let tx = Transaction::new_signed_with_payer(
             &[instruction::delete_pass_book(
                 &nft_pass_book::id(),
-                &self.account.pubkey(),
+                &self.pubkey,
                 &user.pubkey(),
                 &refunder,
+                self.master_edition.as_ref(),
+                refunds,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&user.owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn thaw_pass(
+        &self,
+        context: &mut ProgramTestContext,
+        user: &User,
+        pass_mint: &Pubkey,
+        token_account: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::thaw_pass(
+                &nft_pass_book::id(),
+                pass_mint,
+                &self.pubkey,
+                &user.pubkey(),
+                token_account,
             )],
             Some(&context.payer.pubkey()),
             &[&user.owner, &context.payer],
@@ -136,25 +239,25 @@ impl TestPassBook {
         args: instruction::InitPassBookArgs,
     ) -> Result<(), BanksClientError> {
         let mut instructions: Vec<Instruction> = vec![];
-        let mut signers = vec![&context.payer, &user.owner, &self.account];
+        let mut signers = vec![&context.payer, &user.owner];
+        if self.master_edition.is_none() {
+            signers.push(&self.account);
+        }
         let is_native = cmp_pubkeys(mint, &spl_token::native_mint::id());
-        let creator_payout_key =
-            find_payout_program_address(&nft_pass_book::id(), &user.pubkey(), mint).0;
-        let creator_token_account = if is_native {
-            creator_payout_key
-        } else {
-            instructions.push(create_associated_token_account(
-                &context.payer.pubkey(),
-                &creator_payout_key,
-                mint,
-            ));
-            get_associated_token_address(&creator_payout_key, mint)
-        };
-        let creator_payout = PayoutInfoArgs {
-            authority: user.pubkey(),
-            payout_account: creator_payout_key,
-            token_account: creator_token_account,
+        let creators = match &args.creators {
+            Some(creators) => creators.iter().map(|c| c.address).collect(),
+            None => vec![user.pubkey()],
         };
+        let creator_payouts = creator_payouts(&creators, mint);
+        if !is_native {
+            for creator_payout in creator_payouts.iter() {
+                instructions.push(create_associated_token_account(
+                    &context.payer.pubkey(),
+                    &creator_payout.payout_account,
+                    mint,
+                ));
+            }
+        }
         let market_authority = if let Some(market_info) = market {
             signers.push(&market_info.owner);
             let payout =
@@ -204,19 +307,20 @@ impl TestPassBook {
         // let rent = context.banks_client.get_rent().await?;
         // instructions.push(system_instruction::create_account(
         //     &context.payer.pubkey(),
-        //     &self.account.pubkey(),
+        //     &self.pubkey,
         //     rent.minimum_balance(PassBook::LEN),
         //     PassBook::LEN as u64,
         //     &nft_pass_book::id(),
         // ));
         instructions.push(instruction::init_pass_book(
             &nft_pass_book::id(),
-            &self.account.pubkey(),
+            &self.pubkey,
             store,
             &user.pubkey(),
             &context.payer.pubkey(),
             mint,
-            &creator_payout,
+            self.master_edition.as_ref(),
+            &creator_payouts,
             market_authority.as_ref(),
             referrer.as_ref(),
             args.clone(),
@@ -240,25 +344,175 @@ impl TestPassBook {
         trade_history: &TestTradeHistory,
         args: instruction::BuyPassArgs,
     ) -> Result<(), BanksClientError> {
+        self.buy_with_pass(
+            context,
+            store,
+            buyer,
+            membership,
+            market,
+            trade_history,
+            None,
+            args,
+        )
+        .await
+    }
+
+    pub async fn buy_with_pass(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        buyer: &User,
+        membership: &TestMembership,
+        market: Option<&User>,
+        trade_history: &TestTradeHistory,
+        pass_mint: Option<&PassMintArgs>,
+        args: instruction::BuyPassArgs,
+    ) -> Result<(), BanksClientError> {
+        self.purchase(
+            context,
+            store,
+            buyer,
+            membership,
+            market,
+            trade_history,
+            pass_mint,
+            args,
+            instruction::buy_pass,
+        )
+        .await
+    }
+
+    pub async fn gift(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        buyer: &User,
+        beneficiary: &Pubkey,
+        membership: &TestMembership,
+        market: Option<&User>,
+        trade_history: &TestTradeHistory,
+        args: instruction::BuyPassArgs,
+    ) -> Result<(), BanksClientError> {
+        self.purchase(
+            context,
+            store,
+            buyer,
+            membership,
+            market,
+            trade_history,
+            None,
+            args,
+            |program_id,
+             passbook,
+             store,
+             user_wallet,
+             user_token_account,
+             payer,
+             trade_history,
+             membership,
+             escrow_token_account,
+             market_authority,
+             referral_authority,
+             creator_payouts,
+             pass_mint,
+             previous_passbook,
+             args| {
+                instruction::buy_gift_pass(
+                    program_id,
+                    passbook,
+                    store,
+                    user_wallet,
+                    user_token_account,
+                    payer,
+                    trade_history,
+                    membership,
+                    beneficiary,
+                    escrow_token_account,
+                    market_authority,
+                    referral_authority,
+                    creator_payouts,
+                    pass_mint,
+                    previous_passbook,
+                    args,
+                )
+            },
+        )
+        .await
+    }
+
+    pub async fn renew(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        buyer: &User,
+        membership: &TestMembership,
+        market: Option<&User>,
+        trade_history: &TestTradeHistory,
+        args: instruction::BuyPassArgs,
+    ) -> Result<(), BanksClientError> {
+        let pass_mint = membership.get_pass_mint(context).await;
+        self.purchase(
+            context,
+            store,
+            buyer,
+            membership,
+            market,
+            trade_history,
+            None,
+            args,
+            |program_id,
+             passbook,
+             store,
+             user_wallet,
+             user_token_account,
+             payer,
+             trade_history,
+             membership,
+             escrow_token_account,
+             market_authority,
+             referral_authority,
+             creator_payouts,
+             _,
+             _,
+             args| {
+                instruction::renew_membership(
+                    program_id,
+                    passbook,
+                    store,
+                    user_wallet,
+                    user_token_account,
+                    payer,
+                    trade_history,
+                    membership,
+                    escrow_token_account,
+                    market_authority,
+                    referral_authority,
+                    creator_payouts,
+                    pass_mint.as_ref(),
+                    args,
+                )
+            },
+        )
+        .await
+    }
+
+    /// Payout accounts of the creators, market place and referrer paid by a purchase
+    async fn payout_args(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        market: Option<&User>,
+    ) -> (
+        Vec<PayoutInfoArgs>,
+        Option<PayoutInfoArgs>,
+        Option<PayoutInfoArgs>,
+    ) {
         let passbook: PassBook = self.get_data(context).await;
         let pass_store: Store = store.get_data(context).await;
-        let mut signers = vec![&context.payer, &buyer.owner];
         let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
-        let creator_payout_key =
-            find_payout_program_address(&nft_pass_book::id(), &passbook.authority, &passbook.mint)
-                .0;
-        let token_account = if is_native {
-            creator_payout_key
-        } else {
-            get_associated_token_address(&creator_payout_key, &passbook.mint)
-        };
-        let creator_payout = PayoutInfoArgs {
-            authority: passbook.authority,
-            payout_account: creator_payout_key,
-            token_account: token_account,
-        };
+        let creators: Vec<Pubkey> = passbook.creators.iter().map(|c| c.address).collect();
+        let creator_payouts = creator_payouts(&creators, &passbook.mint);
         let market_authority = if let Some(market_info) = market {
-            signers.push(&market_info.owner);
             let payout = find_payout_program_address(
                 &nft_pass_book::id(),
                 &market_info.pubkey(),
@@ -298,6 +552,211 @@ impl TestPassBook {
             None
         };
 
+        (creator_payouts, market_authority, referrer)
+    }
+
+    /// Token account holding the escrowed revenue, if the pass book uses an escrow
+    pub fn escrow_token_account(&self, passbook: &PassBook) -> Option<Pubkey> {
+        passbook.escrow_hold_period?;
+        let escrow = find_pass_book_escrow(&nft_pass_book::id(), &self.pubkey).0;
+        if cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id()) {
+            Some(escrow)
+        } else {
+            Some(get_associated_token_address(&escrow, &passbook.mint))
+        }
+    }
+
+    pub async fn create_escrow_token_account(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> Result<(), BanksClientError> {
+        let passbook: PassBook = self.get_data(context).await;
+        let escrow = find_pass_book_escrow(&nft_pass_book::id(), &self.pubkey).0;
+        let tx = Transaction::new_signed_with_payer(
+            &[create_associated_token_account(
+                &context.payer.pubkey(),
+                &escrow,
+                &passbook.mint,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn settle(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        market: Option<&User>,
+    ) -> Result<(), BanksClientError> {
+        let passbook: PassBook = self.get_data(context).await;
+        let (creator_payouts, market_authority, referrer) =
+            self.payout_args(context, store, market).await;
+        let escrow_token_account = self
+            .escrow_token_account(&passbook)
+            .unwrap_or_else(|| find_pass_book_escrow(&nft_pass_book::id(), &self.pubkey).0);
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::settle_escrow(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &store.pubkey,
+                &escrow_token_account,
+                market_authority.as_ref(),
+                referrer.as_ref(),
+                &creator_payouts,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn subscribe(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        member: &User,
+        args: instruction::SubscribeArgs,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::subscribe_membership(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &store.pubkey,
+                &member.pubkey(),
+                &member.token_account.pubkey(),
+                args,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &member.owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn charge(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        member: &User,
+        trade_history: &TestTradeHistory,
+        market: Option<&User>,
+    ) -> Result<(), BanksClientError> {
+        let passbook: PassBook = self.get_data(context).await;
+        let (creator_payouts, market_authority, referrer) =
+            self.payout_args(context, store, market).await;
+        let escrow_token_account = self.escrow_token_account(&passbook);
+        let pass_mint = TestMembership::new(&store.pubkey, &member.pubkey())
+            .get_pass_mint(context)
+            .await;
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::charge_subscription(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &store.pubkey,
+                &member.pubkey(),
+                &member.token_account.pubkey(),
+                &context.payer.pubkey(),
+                &trade_history.pubkey,
+                escrow_token_account.as_ref(),
+                market_authority.as_ref(),
+                referrer.as_ref(),
+                &creator_payouts,
+                pass_mint.as_ref(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn refund(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        member: &User,
+        buyer: &User,
+        membership: &TestMembership,
+        trade_history: &TestTradeHistory,
+    ) -> Result<(), BanksClientError> {
+        let passbook: PassBook = self.get_data(context).await;
+        let purchase = membership.get_data(context).await.purchase.unwrap_or_default();
+        let is_native = cmp_pubkeys(&purchase.mint, &spl_token::native_mint::id());
+        let authorities: Vec<Pubkey> = purchase.payouts.iter().map(|p| p.authority).collect();
+        let purchase_payouts = creator_payouts(&authorities, &purchase.mint);
+        let escrow_token_account = self.escrow_token_account(&passbook);
+        let buyer_token = if is_native {
+            buyer.pubkey()
+        } else {
+            buyer.token_account.pubkey()
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::refund_membership(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &store.pubkey,
+                &membership.pubkey,
+                &member.pubkey(),
+                &trade_history.pubkey,
+                &buyer_token,
+                escrow_token_account.as_ref(),
+                &purchase_payouts,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &member.owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    async fn purchase(
+        &self,
+        context: &mut ProgramTestContext,
+        store: &TestStore,
+        buyer: &User,
+        membership: &TestMembership,
+        market: Option<&User>,
+        trade_history: &TestTradeHistory,
+        pass_mint: Option<&PassMintArgs>,
+        args: instruction::BuyPassArgs,
+        build_instruction: impl Fn(
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            &Pubkey,
+            Option<&Pubkey>,
+            Option<&PayoutInfoArgs>,
+            Option<&PayoutInfoArgs>,
+            &[PayoutInfoArgs],
+            Option<&PassMintArgs>,
+            Option<&Pubkey>,
+            instruction::BuyPassArgs,
+        ) -> Instruction,
+    ) -> Result<(), BanksClientError> {
+        let passbook: PassBook = self.get_data(context).await;
+        let is_native = cmp_pubkeys(&passbook.mint, &spl_token::native_mint::id());
+        let (creator_payouts, market_authority, referrer) =
+            self.payout_args(context, store, market).await;
+        let escrow_token_account = self.escrow_token_account(&passbook);
+        let previous_passbook = membership
+            .get_passbook(context)
+            .await
+            .filter(|previous_passbook| *previous_passbook != self.pubkey);
+        let signers = vec![&context.payer, &buyer.owner];
+
         let buyer_token = if is_native {
             buyer.pubkey()
         } else {
@@ -305,18 +764,21 @@ impl TestPassBook {
         };
         println!("START PROCESSING BUY INSTRUCTION");
         let tx = Transaction::new_signed_with_payer(
-            &[instruction::buy_pass(
+            &[build_instruction(
                 &nft_pass_book::id(),
-                &self.account.pubkey(),
+                &self.pubkey,
                 &store.pubkey,
                 &buyer.pubkey(),
                 &buyer_token,
                 &context.payer.pubkey(),
                 &trade_history.pubkey,
                 &membership.pubkey,
+                escrow_token_account.as_ref(),
                 market_authority.as_ref(),
                 referrer.as_ref(),
-                &creator_payout,
+                &creator_payouts,
+                pass_mint,
+                previous_passbook.as_ref(),
                 args.clone(),
             )],
             Some(&context.payer.pubkey()),
@@ -327,3 +789,25 @@ impl TestPassBook {
         context.banks_client.process_transaction(tx).await
     }
 }
+
+/// Payout accounts of the creators for the given mint
+pub fn creator_payouts(creators: &[Pubkey], mint: &Pubkey) -> Vec<PayoutInfoArgs> {
+    let is_native = cmp_pubkeys(mint, &spl_token::native_mint::id());
+    creators
+        .iter()
+        .map(|creator| {
+            let payout_account =
+                find_payout_program_address(&nft_pass_book::id(), creator, mint).0;
+            let token_account = if is_native {
+                payout_account
+            } else {
+                get_associated_token_address(&payout_account, mint)
+            };
+            PayoutInfoArgs {
+                authority: *creator,
+                payout_account,
+                token_account,
+            }
+        })
+        .collect()
+}
@@ -1,7 +1,14 @@
-use nft_pass_book::{find_payout_program_address, state::Payout};
+use nft_pass_book::{
+    find_payout_program_address,
+    instruction::{self, WithdrawArgs},
+    state::Payout,
+    utils::cmp_pubkeys,
+};
 use solana_program::program_pack::Pack;
-use solana_program_test::ProgramTestContext;
-use solana_sdk::pubkey::Pubkey;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Signer, signer::keypair::Keypair, transaction::Transaction,
+};
 
 use super::get_account;
 
@@ -21,4 +28,31 @@ impl TestPayout {
         let account = get_account(context, &self.pubkey).await;
         Payout::unpack_unchecked(&account.data).unwrap()
     }
+
+    pub async fn withdraw(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        destination: &Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let payout = self.get_data(context).await;
+        let is_native = cmp_pubkeys(&payout.mint, &spl_token::native_mint::id());
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::withdraw(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &payout.treasury_holder,
+                &authority.pubkey(),
+                destination,
+                is_native,
+                WithdrawArgs { amount },
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
 }
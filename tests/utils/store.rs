@@ -1,7 +1,16 @@
-use nft_pass_book::{find_pass_store_program_address, state::Store};
+use nft_pass_book::{
+    find_pass_store_program_address,
+    instruction::{
+        self, AddStoreAuthorityArgs, CreateStoreCollectionArgs, SetExpiryBountyArgs,
+        TopUpStoreAuthorityArgs,
+    },
+    state::Store,
+};
 use solana_program::program_pack::Pack;
-use solana_program_test::ProgramTestContext;
-use solana_sdk::pubkey::Pubkey;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Signer, signer::keypair::Keypair, transaction::Transaction,
+};
 
 use super::get_account;
 
@@ -21,4 +30,128 @@ impl TestStore {
         let account = get_account(context, &self.pubkey).await;
         Store::unpack_unchecked(&account.data).unwrap()
     }
+
+    pub async fn add_store_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        store_authority_record: &Pubkey,
+        authority: &Keypair,
+        user: &Pubkey,
+        allowed_redemptions: u64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::add_store_authority(
+                &nft_pass_book::id(),
+                store_authority_record,
+                &self.pubkey,
+                &authority.pubkey(),
+                user,
+                &context.payer.pubkey(),
+                AddStoreAuthorityArgs {
+                    allowed_redemptions,
+                },
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn top_up_store_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        store_authority_record: &Pubkey,
+        authority: &Keypair,
+        user: &Pubkey,
+        additional_redemptions: u64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::top_up_store_authority(
+                &nft_pass_book::id(),
+                store_authority_record,
+                &self.pubkey,
+                &authority.pubkey(),
+                user,
+                TopUpStoreAuthorityArgs {
+                    additional_redemptions,
+                },
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn remove_store_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        store_authority_record: &Pubkey,
+        authority: &Keypair,
+        user: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::remove_store_authority(
+                &nft_pass_book::id(),
+                store_authority_record,
+                &self.pubkey,
+                &authority.pubkey(),
+                user,
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_expiry_bounty(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        bounty: u64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_expiry_bounty(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &authority.pubkey(),
+                SetExpiryBountyArgs { bounty },
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn create_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::create_store_collection(
+                &nft_pass_book::id(),
+                &self.pubkey,
+                &authority.pubkey(),
+                &context.payer.pubkey(),
+                CreateStoreCollectionArgs {
+                    name: String::from("Store Passes"),
+                    symbol: String::from("PASS"),
+                    uri: String::from("some link to storage"),
+                },
+            )],
+            Some(&context.payer.pubkey()),
+            &[authority, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
 }
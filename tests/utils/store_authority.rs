@@ -0,0 +1,24 @@
+use nft_pass_book::{find_store_authority_program_address, state::StoreAuthority};
+use solana_program::program_pack::Pack;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::pubkey::Pubkey;
+
+use super::get_account;
+
+#[derive(Debug)]
+pub struct TestStoreAuthority {
+    pub pubkey: Pubkey,
+}
+
+impl TestStoreAuthority {
+    pub fn new(store: &Pubkey, user: &Pubkey) -> Self {
+        let (pubkey, _) = find_store_authority_program_address(&nft_pass_book::id(), store, user);
+
+        TestStoreAuthority { pubkey }
+    }
+
+    pub async fn get_data(&self, context: &mut ProgramTestContext) -> StoreAuthority {
+        let account = get_account(context, &self.pubkey).await;
+        StoreAuthority::unpack_unchecked(&account.data).unwrap()
+    }
+}
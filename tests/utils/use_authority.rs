@@ -0,0 +1,25 @@
+use nft_pass_book::{find_use_authority_program_address, state::UseAuthority};
+use solana_program::program_pack::Pack;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::pubkey::Pubkey;
+
+use super::get_account;
+
+#[derive(Debug)]
+pub struct TestUseAuthority {
+    pub pubkey: Pubkey,
+}
+
+impl TestUseAuthority {
+    pub fn new(membership: &Pubkey, user: &Pubkey) -> Self {
+        let (pubkey, _) =
+            find_use_authority_program_address(&nft_pass_book::id(), membership, user);
+
+        TestUseAuthority { pubkey }
+    }
+
+    pub async fn get_data(&self, context: &mut ProgramTestContext) -> UseAuthority {
+        let account = get_account(context, &self.pubkey).await;
+        UseAuthority::unpack_unchecked(&account.data).unwrap()
+    }
+}
@@ -0,0 +1,112 @@
+mod utils;
+
+use nft_pass_book::error::NFTPassError;
+use num_traits::FromPrimitive;
+use solana_program::{instruction::InstructionError, program_pack::Pack};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn success_with_spl_token() {
+    let (mut context, test_pass, _, _, creator, _) = setup_membership(Some(3)).await;
+    let passbook = test_pass.get_data(&mut context).await;
+    let test_creator_payout = TestPayout::new(&creator.pubkey(), &passbook.mint);
+
+    let account = get_account(&mut context, &creator.token_account.pubkey()).await;
+    let initial_amount = spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount;
+
+    test_creator_payout
+        .withdraw(
+            &mut context,
+            &creator.owner,
+            &creator.token_account.pubkey(),
+            9_000_000,
+        )
+        .await
+        .unwrap();
+
+    let payout = test_creator_payout.get_data(&mut context).await;
+    assert_eq!(payout.cash_in, 9_750_000);
+    assert_eq!(payout.cash_out, 9_000_000);
+
+    let account = get_account(&mut context, &creator.token_account.pubkey()).await;
+    let amount = spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount;
+    assert_eq!(amount - initial_amount, 9_000_000);
+}
+
+#[tokio::test]
+async fn success_with_native_token() {
+    let (mut context, test_pass, _, _, creator, _) =
+        setup_membership_with_mint(Some(3), true).await;
+    let passbook = test_pass.get_data(&mut context).await;
+    let test_creator_payout = TestPayout::new(&creator.pubkey(), &passbook.mint);
+    let destination = Keypair::new();
+
+    test_creator_payout
+        .withdraw(
+            &mut context,
+            &creator.owner,
+            &destination.pubkey(),
+            9_750_000,
+        )
+        .await
+        .unwrap();
+
+    let payout = test_creator_payout.get_data(&mut context).await;
+    assert_eq!(payout.cash_out, 9_750_000);
+
+    let destination_account = get_account(&mut context, &destination.pubkey()).await;
+    assert_eq!(destination_account.lamports, 9_750_000);
+}
+
+#[tokio::test]
+async fn failure_insufficient_balance() {
+    let (mut context, test_pass, _, _, creator, _) = setup_membership(Some(3)).await;
+    let passbook = test_pass.get_data(&mut context).await;
+    let test_creator_payout = TestPayout::new(&creator.pubkey(), &passbook.mint);
+
+    let result = test_creator_payout
+        .withdraw(
+            &mut context,
+            &creator.owner,
+            &creator.token_account.pubkey(),
+            9_750_001,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InsufficientPayoutBalance,
+        0
+    );
+}
+
+#[tokio::test]
+async fn failure_invalid_authority() {
+    let (mut context, test_pass, _, _, creator, _) = setup_membership(Some(3)).await;
+    let passbook = test_pass.get_data(&mut context).await;
+    let test_creator_payout = TestPayout::new(&creator.pubkey(), &passbook.mint);
+
+    let result = test_creator_payout
+        .withdraw(
+            &mut context,
+            &Keypair::new(),
+            &creator.token_account.pubkey(),
+            1,
+        )
+        .await;
+
+    assert_custom_error!(
+        result.unwrap_err().unwrap(),
+        NFTPassError::InvalidCreatorKey,
+        0
+    );
+}